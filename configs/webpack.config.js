@@ -1,5 +1,8 @@
 const path = require("path");
 const dist = path.resolve(__dirname, "../dist");
+// The path the app is served from, e.g. `/conduit/` for `CONDUIT_BASE_PATH=/conduit`.
+const trimmedBasePath = (process.env.CONDUIT_BASE_PATH || "").replace(/^\/+|\/+$/g, "");
+const basePath = trimmedBasePath ? `/${trimmedBasePath}/` : "/";
 
 const WebpackBar = require("webpackbar");
 const HtmlWebpackPlugin = require("html-webpack-plugin");
@@ -16,10 +19,13 @@ module.exports = (env, argv) => {
     output: {
       // You can deploy your site from this folder (after build with e.g. `yarn build:release`)
       path: dist,
-      filename:'[name].[contenthash].js'
+      filename:'[name].[contenthash].js',
+      // Resolve bundles, wasm and assets relative to the base path.
+      publicPath: basePath
     },
     devServer: {
       contentBase: dist,
+      publicPath: basePath,
       // You can connect to dev server from devices in your network (e.g. 192.168.0.3:8000).
       host: "0.0.0.0",
       port: 8000,
@@ -29,7 +35,9 @@ module.exports = (env, argv) => {
         warnings: true,
         errors: true
       },
-      historyApiFallback: true,
+      historyApiFallback: {
+        index: basePath
+      },
     },
     plugins: [
       // Show compilation progress bar in console.
@@ -38,7 +46,9 @@ module.exports = (env, argv) => {
       new CleanWebpackPlugin(),
       // Add scripts, css, ... to html template.
       new HtmlWebpackPlugin({
-        template: path.resolve(__dirname, "../entries/index.hbs")
+        template: path.resolve(__dirname, "../entries/index.hbs"),
+        // Used as `<base href>` in the template.
+        basePath
       }),
       // Compile Rust.
       new WasmPackPlugin({
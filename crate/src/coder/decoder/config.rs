@@ -0,0 +1,119 @@
+use crate::{
+    config::{self, Profile},
+    entity::ErrorMessage,
+};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::str::FromStr;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    profile: Option<String>,
+    api_url: Option<String>,
+    timeout: Option<u32>,
+    base_path: Option<String>,
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+}
+
+impl Config {
+    /// Values missing in the decoded JSON are taken from the `profile`'s
+    /// defaults or from `default` when no profile is specified.
+    pub fn try_into_config(
+        self,
+        default: config::Config,
+    ) -> Result<config::Config, ErrorMessage> {
+        let mut config = match self.profile {
+            Some(profile) => Profile::from_str(&profile)
+                .map(config::Config::new)
+                .map_err(|_| format!("unknown config profile `{}`", profile))?,
+            None => default,
+        };
+        if let Some(base_path) = self.base_path {
+            config.set_base_path(&base_path);
+        }
+        if let Some(api_url) = self.api_url {
+            config.base_api_url =
+                api_url.trim_end_matches('/').to_owned().into();
+        }
+        if let Some(timeout) = self.timeout {
+            config.timeout = timeout;
+        }
+        config.headers.extend(self.headers);
+        Ok(config)
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use serde_json::{self, json};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn decode_config_test() {
+        // ====== ARRANGE ======
+        let json = json!({
+            "profile": "local",
+            "apiUrl": "http://staging.example.com/api/",
+            "basePath": "/conduit/",
+            "headers": {
+                "x-client": "conduit"
+            },
+        });
+
+        // ====== ACT ======
+        let config = serde_json::from_value::<Config>(json)
+            .expect("deserialize Config failed")
+            .try_into_config(config::Config::new(Profile::Dark))
+            .expect("convert into config failed");
+
+        // ====== ASSERT ======
+        assert_eq!(config.profile, Profile::Local);
+        assert_eq!(config.base_api_url, "http://staging.example.com/api");
+        assert_eq!(config.base_path, "/conduit");
+        assert_eq!(
+            config.headers,
+            vec![("x-client".to_owned(), "conduit".to_owned())]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_config_mock_base_path_test() {
+        // ====== ARRANGE ======
+        let json = json!({
+            "profile": "mock",
+            "basePath": "/conduit/",
+        });
+
+        // ====== ACT ======
+        let config = serde_json::from_value::<Config>(json)
+            .expect("deserialize Config failed")
+            .try_into_config(config::Config::new(Profile::Dark))
+            .expect("convert into config failed");
+
+        // ====== ASSERT ======
+        assert_eq!(config.base_api_url, "/conduit/api");
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_config_unknown_profile_test() {
+        // ====== ARRANGE ======
+        let json = json!({
+            "profile": "production",
+        });
+
+        // ====== ACT ======
+        let result = serde_json::from_value::<Config>(json)
+            .expect("deserialize Config failed")
+            .try_into_config(config::Config::new(Profile::Dark));
+
+        // ====== ASSERT ======
+        assert!(result.is_err());
+    }
+}
@@ -9,13 +9,8 @@ pub struct ErrorMessages {
 }
 
 impl ErrorMessages {
-    pub fn into_error_messages(self) -> Vec<entity::ErrorMessage> {
-        self.errors
-            .into_iter()
-            .map(|(field, errors)| {
-                format!("{} {}", field, errors.join(", ")).into()
-            })
-            .collect()
+    pub fn into_api_error(self) -> entity::ApiError {
+        entity::ApiError::Validation(self.errors)
     }
 }
 
@@ -7,6 +7,9 @@ pub use author::Author;
 mod comment;
 pub use comment::Comment;
 
+mod config;
+pub use config::Config;
+
 mod error_messages;
 pub use error_messages::ErrorMessages;
 
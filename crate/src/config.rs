@@ -0,0 +1,193 @@
+use crate::{coder::decoder, logger};
+use std::{borrow::Cow, cell::RefCell, rc::Rc, str::FromStr};
+use strum_macros::EnumString;
+
+// Runtime configuration can be provided by the host page in two ways
+// (the global object wins if both are present):
+//
+// - `<script>window.CONDUIT_CONFIG = { "profile": "local" };</script>`
+// - `<meta name="conduit-config" content='{ "apiUrl": "https://..." }'>`
+//
+// Compile-time defaults can be set by env variables `CONDUIT_PROFILE`,
+// `CONDUIT_API_URL` and `CONDUIT_BASE_PATH`.
+const GLOBAL_OBJECT_NAME: &str = "CONDUIT_CONFIG";
+const META_TAG_SELECTOR: &str = "meta[name='conduit-config']";
+
+const DEFAULT_TIMEOUT: u32 = 5000;
+
+thread_local! {
+    static CONFIG: RefCell<Rc<Config>> = RefCell::new(Rc::new(Config::default()));
+}
+
+/// Load configuration from the host page and make it `current`.
+/// It should be called once, before the first request is sent.
+pub fn init() {
+    let config = load();
+    CONFIG.with(|current| current.replace(Rc::new(config)));
+}
+
+pub fn current() -> Rc<Config> {
+    CONFIG.with(|current| Rc::clone(&current.borrow()))
+}
+
+// ------ Profile ------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Profile {
+    /// The public Dark lang backend.
+    Dark,
+    /// An API server running on the developer's machine.
+    Local,
+    /// A mock API served from the same origin as the app.
+    Mock,
+}
+
+impl Profile {
+    pub const fn base_api_url(self) -> &'static str {
+        match self {
+            Self::Dark => "https://martinkavik-realworld.builtwithdark.com/api",
+            Self::Local => "http://localhost:3000/api",
+            Self::Mock => "/api",
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        option_env!("CONDUIT_PROFILE")
+            .and_then(|profile| Self::from_str(profile).ok())
+            .unwrap_or(Self::Dark)
+    }
+}
+
+// ------ Config ------
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub profile: Profile,
+    pub base_api_url: Cow<'static, str>,
+    pub timeout: u32,
+    pub headers: Vec<(String, String)>,
+    /// The path the app is served from, e.g. "/conduit".
+    /// It's empty for the domain root.
+    pub base_path: Cow<'static, str>,
+}
+
+impl Config {
+    pub fn new(profile: Profile) -> Self {
+        Self {
+            profile,
+            base_api_url: profile.base_api_url().into(),
+            timeout: DEFAULT_TIMEOUT,
+            headers: Vec::new(),
+            base_path: "".into(),
+        }
+    }
+
+    /// "conduit/" => "/conduit", "/" => ""
+    ///
+    /// Call it before setting a custom `base_api_url`.
+    pub fn set_base_path(&mut self, base_path: &str) {
+        let base_path = base_path.trim_matches('/');
+        self.base_path = if base_path.is_empty() {
+            "".into()
+        } else {
+            format!("/{}", base_path).into()
+        };
+        // the mock API is served from the app's origin, under its base path
+        if self.profile == Profile::Mock {
+            self.base_api_url =
+                format!("{}{}", self.base_path, Profile::Mock.base_api_url())
+                    .into();
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let mut config = Self::new(Profile::default());
+        if let Some(base_path) = option_env!("CONDUIT_BASE_PATH") {
+            config.set_base_path(base_path);
+        }
+        if let Some(base_api_url) = option_env!("CONDUIT_API_URL") {
+            config.base_api_url = base_api_url.into();
+        }
+        config
+    }
+}
+
+// ====== PRIVATE ======
+
+fn load() -> Config {
+    let json = match global_object_json().or_else(meta_tag_json) {
+        Some(json) => json,
+        None => return Config::default(),
+    };
+    serde_json::from_str::<decoder::Config>(&json)
+        .map_err(|serde_error| serde_error.to_string().into())
+        .and_then(|config_decoder| {
+            config_decoder.try_into_config(Config::default())
+        })
+        .unwrap_or_else(|error| {
+            logger::error(error);
+            Config::default()
+        })
+}
+
+fn global_object_json() -> Option<String> {
+    let object = seed::window().get(GLOBAL_OBJECT_NAME)?;
+    js_sys::JSON::stringify(&object).ok().and_then(|json| json.as_string())
+}
+
+fn meta_tag_json() -> Option<String> {
+    seed::document()
+        .query_selector(META_TAG_SELECTOR)
+        .ok()
+        .flatten()
+        .and_then(|element| element.get_attribute("content"))
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn profile_from_str_test() {
+        // ====== ACT ======
+        let profile = Profile::from_str("local");
+
+        // ====== ASSERT ======
+        assert_eq!(profile, Ok(Profile::Local));
+    }
+
+    #[wasm_bindgen_test]
+    fn new_config_test() {
+        // ====== ACT ======
+        let config = Config::new(Profile::Mock);
+
+        // ====== ASSERT ======
+        assert_eq!(config.base_api_url, "/api");
+        assert_eq!(config.timeout, DEFAULT_TIMEOUT);
+        assert!(config.headers.is_empty());
+        assert_eq!(config.base_path, "");
+    }
+
+    #[wasm_bindgen_test]
+    fn set_base_path_test() {
+        // ====== ARRANGE ======
+        let mut config = Config::new(Profile::Mock);
+
+        // ====== ACT & ASSERT ======
+        config.set_base_path("conduit/");
+        assert_eq!(config.base_path, "/conduit");
+
+        config.set_base_path("/");
+        assert_eq!(config.base_path, "");
+    }
+}
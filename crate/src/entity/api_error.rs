@@ -0,0 +1,114 @@
+use crate::{
+    entity::{form::Problem, ErrorMessage},
+    i18n,
+};
+use indexmap::IndexMap;
+
+// ------ ApiError ------
+
+/// Reason why a request to the API failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApiError {
+    /// The request hasn't reached the server (offline, DNS, CORS, ..).
+    Network,
+    /// The server hasn't responded before the configured timeout.
+    Timeout,
+    /// 401 - the auth token is missing, invalid or expired.
+    Unauthorized,
+    /// 403 - the viewer isn't allowed to do it (e.g. delete foreign article).
+    Forbidden,
+    /// 404
+    NotFound,
+    /// The server has rejected data - messages are grouped by field names.
+    Validation(IndexMap<String, Vec<String>>),
+    /// Other unexpected status code (e.g. 500).
+    Status(u16),
+    /// The response doesn't match the expected shape.
+    Decode {
+        /// Path to the offending value, e.g. `article.createdAt`.
+        path: String,
+        message: String,
+    },
+}
+
+impl ApiError {
+    pub fn new_decode(
+        path: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::Decode {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn into_error_messages(self) -> Vec<ErrorMessage> {
+        match self {
+            Self::Network => vec![i18n::t("error.network").into()],
+            Self::Timeout => vec![i18n::t("error.timeout").into()],
+            Self::Unauthorized => vec![i18n::t("error.unauthorized").into()],
+            Self::Forbidden => {
+                vec![i18n::t("error.forbidden").into()]
+            },
+            Self::NotFound => vec![i18n::t("error.not_found").into()],
+            Self::Validation(errors) => errors
+                .into_iter()
+                .map(|(field, errors)| {
+                    format!("{} {}", field, errors.join(", ")).into()
+                })
+                .collect(),
+            Self::Status(code) => {
+                vec![i18n::t_with("error.status", &[("code", &code)]).into()]
+            },
+            Self::Decode {
+                path,
+                ..
+            } => vec![i18n::t_with("error.decode", &[("path", &path)]).into()],
+        }
+    }
+
+    pub fn into_problems(self) -> Vec<Problem> {
+        self.into_error_messages()
+            .into_iter()
+            .map(|error| Problem::new_server_error(error.into_inner()))
+            .collect()
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn validation_into_error_messages_test() {
+        // ====== ARRANGE ======
+        let mut errors = IndexMap::new();
+        errors.insert(
+            "email".to_owned(),
+            vec!["can't be blank".to_owned(), "is invalid".to_owned()],
+        );
+        errors.insert(
+            "username".to_owned(),
+            vec!["has already been taken".to_owned()],
+        );
+
+        // ====== ACT ======
+        let error_messages = ApiError::Validation(errors).into_error_messages();
+
+        // ====== ASSERT ======
+        let error_messages = error_messages
+            .into_iter()
+            .map(ErrorMessage::into_inner)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            error_messages,
+            vec![
+                "email can't be blank, is invalid",
+                "username has already been taken"
+            ]
+        );
+    }
+}
@@ -25,6 +25,18 @@ pub struct Article {
 }
 
 impl Article {
+    pub fn set_favorited(&mut self, favorited: bool) {
+        if self.favorited == favorited {
+            return;
+        }
+        self.favorited = favorited;
+        if favorited {
+            self.favorites_count += 1;
+        } else {
+            self.favorites_count = self.favorites_count.saturating_sub(1);
+        }
+    }
+
     pub fn into_form(self) -> Form {
         Form::new(vec![
             Field::Title(self.title),
@@ -1,5 +1,15 @@
-use crate::entity::{Author, Timestamp};
+use crate::entity::{Author, Slug, Timestamp, Viewer};
+use chrono::Local;
 use newtype::NewType;
+use std::cell::Cell;
+
+const PENDING_ID_PREFIX: &str = "pending-";
+const QUEUED_ID_PREFIX: &str = "queued-";
+const ANCHOR_PREFIX: &str = "comment-";
+
+thread_local! {
+    static LAST_PENDING_ID: Cell<usize> = const { Cell::new(0) };
+}
 
 // ------ Comment ------
 
@@ -12,12 +22,60 @@ pub struct Comment {
     pub author: Author,
 }
 
+impl Comment {
+    /// Comment displayed before the server confirms that it has been created.
+    pub fn new_pending(id: CommentId, body: String, viewer: Viewer) -> Self {
+        Self {
+            id,
+            body,
+            created_at: Local::now().into(),
+            updated_at: Local::now().into(),
+            author: Author::IsViewer(viewer),
+        }
+    }
+}
+
 // ------ CommentId ------
 
-#[derive(NewType, Clone, PartialEq, Eq)]
+#[derive(NewType, Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(clippy::module_name_repetitions)]
 pub struct CommentId(String);
 
+impl CommentId {
+    /// Id of a comment posted to the article with `slug`
+    /// and not confirmed by the server yet, e.g. "pending-my-article-3".
+    /// The local id is unique, so ids from previous page visits don't clash.
+    pub fn new_pending(slug: &Slug) -> Self {
+        let local_id = LAST_PENDING_ID.with(|last_id| {
+            last_id.set(last_id.get() + 1);
+            last_id.get()
+        });
+        Self(format!("{}{}-{}", PENDING_ID_PREFIX, slug.as_str(), local_id))
+    }
+
+    /// Id of the article's comment at `index` in the outbox.
+    pub fn new_queued(slug: &Slug, index: usize) -> Self {
+        Self(format!("{}{}-{}", QUEUED_ID_PREFIX, slug.as_str(), index))
+    }
+
+    /// Pending or queued comment.
+    pub fn is_pending(&self) -> bool {
+        self.0.starts_with(PENDING_ID_PREFIX)
+            || self.0.starts_with(QUEUED_ID_PREFIX)
+    }
+
+    /// Id of the rendered comment element, e.g. "comment-42".
+    pub fn anchor(&self) -> String {
+        format!("{}{}", ANCHOR_PREFIX, self.0)
+    }
+
+    /// "comment-42" => `CommentId` 42
+    pub fn from_anchor(anchor: &str) -> Option<Self> {
+        let id = anchor.strip_prefix(ANCHOR_PREFIX)?;
+        id.parse::<usize>().ok().map(Self::from)
+    }
+}
+
 impl From<usize> for CommentId {
     fn from(id: usize) -> Self {
         Self(id.to_string())
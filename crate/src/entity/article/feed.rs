@@ -1,9 +1,14 @@
 use crate::{
     entity::{
-        author, timestamp, Article, ErrorMessage, PageNumber, PaginatedList,
-        Slug, Tag, Viewer,
+        author, markdown, timestamp, ApiError, Article, PageNumber,
+        PaginatedList, Slug, Tag, Viewer,
     },
-    logger, page, request, GMsg, Route, Session,
+    i18n, logger,
+    outbox::Mutation,
+    request,
+    store::Store,
+    toast::Toast,
+    GMsg, Route, Session,
 };
 use seed::prelude::*;
 use std::borrow::Cow;
@@ -15,19 +20,21 @@ use std::borrow::Cow;
 #[derive(Default)]
 pub struct Model {
     session: Session,
-    errors: Vec<ErrorMessage>,
-    articles: PaginatedList<Article>,
+    articles: PaginatedList<Slug>,
 }
 
 // ------ ------
 //     Init
 // ------ ------
 
-pub fn init(session: Session, articles: PaginatedList<Article>) -> Model {
+pub fn init(
+    session: Session,
+    articles: PaginatedList<Article>,
+    store: &mut Store,
+) -> Model {
     Model {
         session,
-        articles,
-        ..Model::default()
+        articles: store.insert_articles(articles),
     }
 }
 
@@ -37,51 +44,57 @@ pub fn init(session: Session, articles: PaginatedList<Article>) -> Model {
 
 #[derive(Clone)]
 pub enum Msg {
-    DismissErrorsClicked,
     FavoriteClicked(Slug),
     UnfavoriteClicked(Slug),
-    FavoriteCompleted(Result<Article, Vec<ErrorMessage>>),
+    /// The first article is the original - `favorited` is restored on error.
+    FavoriteCompleted(Article, Result<Article, ApiError>),
 }
 
-#[allow(clippy::option_map_unit_fn, clippy::find_map)]
 pub fn update(
     msg: Msg,
     model: &mut Model,
+    store: &mut Store,
     orders: &mut impl Orders<Msg, GMsg>,
 ) {
     match msg {
-        Msg::DismissErrorsClicked => {
-            model.errors.clear();
-        },
         Msg::FavoriteClicked(slug) => {
-            orders
-                .perform_cmd(request::favorite::unfavorite(
+            if let Some(original) = store
+                .change_article(&slug, |article| article.set_favorited(false))
+            {
+                orders.perform_cmd(request::favorite::unfavorite(
                     model.session.viewer().cloned(),
                     &slug,
-                    Msg::FavoriteCompleted,
-                ))
-                .skip();
+                    move |result| Msg::FavoriteCompleted(original, result),
+                ));
+            }
         },
         Msg::UnfavoriteClicked(slug) => {
-            orders
-                .perform_cmd(request::favorite::favorite(
+            if let Some(original) = store
+                .change_article(&slug, |article| article.set_favorited(true))
+            {
+                orders.perform_cmd(request::favorite::favorite(
                     model.session.viewer().cloned(),
                     &slug,
-                    Msg::FavoriteCompleted,
-                ))
-                .skip();
+                    move |result| Msg::FavoriteCompleted(original, result),
+                ));
+            }
+        },
+        Msg::FavoriteCompleted(_, Ok(article)) => {
+            store.insert_article(article);
         },
-        Msg::FavoriteCompleted(Ok(article)) => {
-            model
-                .articles
-                .items
-                .iter_mut()
-                .find(|old_article| old_article.slug == article.slug)
-                .map(|old_article| *old_article = article);
+        // keep the change, it will be sent once the browser is online
+        Msg::FavoriteCompleted(original, Err(ApiError::Network)) => {
+            orders.send_g_msg(GMsg::MutationQueued(
+                Mutation::new_favorite_change(&original),
+            ));
         },
-        Msg::FavoriteCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors;
+        Msg::FavoriteCompleted(original, Err(error)) => {
+            store.restore_favorited(&original);
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(Toast::new_error(
+                i18n::t("toast.favorite_failed"),
+                error,
+            )));
         },
     }
 }
@@ -122,25 +135,20 @@ pub fn view_tabs<Ms: Clone>(tabs: Vec<Tab<Ms>>) -> Node<Ms> {
     ]
 }
 
-pub fn view_articles(model: &Model) -> Vec<Node<Msg>> {
-    vec![page::view_errors(Msg::DismissErrorsClicked, &model.errors)]
-        .into_iter()
-        .chain(if model.articles.total == 0 {
-            vec![div![
-                class!["article-preview"],
-                "No articles are here... yet."
-            ]]
-        } else {
-            model
-                .articles
-                .items
-                .iter()
-                .map(|article| {
-                    view_article_preview(model.session.viewer(), article)
-                })
-                .collect()
-        })
-        .collect()
+pub fn view_articles(model: &Model, store: &Store) -> Vec<Node<Msg>> {
+    if model.articles.total == 0 {
+        vec![div![class!["article-preview"], i18n::t("feed.empty")]]
+    } else {
+        model
+            .articles
+            .items
+            .iter()
+            .filter_map(|slug| store.article(slug))
+            .map(|article| {
+                view_article_preview(model.session.viewer(), article)
+            })
+            .collect()
+    }
 }
 
 pub fn view_pagination<Ms: Clone>(
@@ -191,22 +199,30 @@ fn view_article_preview(
         div![
             class!["article-meta"],
             a![
-                attrs! {At::Href => Route::Profile(Cow::Borrowed(article.author.username())).to_string()},
+                attrs! {At::Href => Route::Profile(Cow::Borrowed(article.author.username()), Default::default()).to_string()},
                 img![attrs! {At::Src => article.author.profile().avatar.src()}]
             ],
             div![
                 class!["info"],
                 author::view(article.author.username()),
-                timestamp::view(&article.created_at)
+                span![
+                    class!["date"],
+                    timestamp::view(&article.created_at),
+                    timestamp::view_edited(
+                        &article.created_at,
+                        &article.updated_at
+                    )
+                ],
+                markdown::view_reading_time(&article.body),
             ],
             view_favorite_button(viewer, article)
         ],
         a![
             class!["preview-link"],
-            attrs! {At::Href => Route::Article(article.slug.clone()).to_string()},
+            attrs! {At::Href => Route::Article(article.slug.clone(), None).to_string()},
             h1![article.title],
             p![article.description],
-            span!["Read more..."],
+            span![i18n::t("feed.read_more")],
             ul![class!["tag-list"], article.tag_list.iter().map(view_tag)]
         ]
     ]
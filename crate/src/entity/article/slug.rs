@@ -1,4 +1,7 @@
 use newtype::NewType;
+use serde::{Deserialize, Serialize};
 
-#[derive(NewType, Clone, Default, PartialEq, Eq, Debug)]
+#[derive(
+    NewType, Clone, Default, PartialEq, Eq, Hash, Debug, Serialize, Deserialize,
+)]
 pub struct Slug(String);
@@ -2,9 +2,15 @@ use std::fmt;
 
 // ------ Tag ------
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Tag(String);
 
+impl From<String> for Tag {
+    fn from(tag: String) -> Self {
+        Self(tag)
+    }
+}
+
 impl fmt::Display for Tag {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.0.fmt(f)
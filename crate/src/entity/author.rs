@@ -1,6 +1,6 @@
 use crate::{
     entity::{Profile, Username, Viewer},
-    Route,
+    i18n, Route,
 };
 use seed::prelude::*;
 use std::borrow::Cow;
@@ -24,6 +24,18 @@ impl Author {
         }
     }
 
+    /// Viewers can't follow themselves - `IsViewer` isn't changed.
+    pub fn set_following(&mut self, following: bool) {
+        if let Self::Following(profile) | Self::NotFollowing(profile) = self {
+            let profile = profile.clone();
+            *self = if following {
+                Self::Following(profile)
+            } else {
+                Self::NotFollowing(profile)
+            };
+        }
+    }
+
     pub fn profile(&self) -> &Profile {
         match self {
             Self::Following(profile) | Self::NotFollowing(profile) => profile,
@@ -37,7 +49,7 @@ impl Author {
 pub fn view<Ms>(username: &Username) -> Node<Ms> {
     a![
         class!["author"],
-        attrs! {At::Href => Route::Profile(Cow::Borrowed(username)).to_string()},
+        attrs! {At::Href => Route::Profile(Cow::Borrowed(username), Default::default()).to_string()},
         username.to_string()
     ]
 }
@@ -46,7 +58,10 @@ pub fn view_follow_button<Ms: Clone>(msg: Ms, username: &Username) -> Node<Ms> {
     button![
         class!["btn", "btn-sm", "btn-outline-secondary", "action-btn"],
         i![class!["ion-plus-round"]],
-        format!("\u{00A0}Follow {}", username.as_str()),
+        format!(
+            "\u{00A0}{}",
+            i18n::t_with("author.follow", &[("username", &username.as_str())])
+        ),
         simple_ev(Ev::Click, msg)
     ]
 }
@@ -58,7 +73,13 @@ pub fn view_unfollow_button<Ms: Clone>(
     button![
         class!["btn", "btn-sm", "btn-secondary", "action-btn"],
         i![class!["ion-plus-round"]],
-        format!("\u{00A0}Unfollow {}", username.as_str()),
+        format!(
+            "\u{00A0}{}",
+            i18n::t_with(
+                "author.unfollow",
+                &[("username", &username.as_str())]
+            )
+        ),
         simple_ev(Ev::Click, msg)
     ]
 }
@@ -1,4 +1,5 @@
 use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Cow;
 
 pub mod article_editor;
@@ -22,6 +23,7 @@ pub trait FormField: Clone {
 
 // ------ Form ------
 
+#[derive(Clone)]
 pub struct Form<T: FormField>(IndexMap<FieldKey, T>);
 
 impl<T: FormField> Form<T> {
@@ -51,6 +53,25 @@ impl<T: FormField> Form<T> {
     }
 }
 
+// Forms are serialized as lists of fields, so they can be saved as drafts.
+
+impl<T: FormField + Serialize> Serialize for Form<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.values())
+    }
+}
+
+impl<'de, T: FormField + Deserialize<'de>> Deserialize<'de> for Form<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(Self::new)
+    }
+}
+
 // ------ TrimmedForm ------
 
 #[allow(clippy::module_name_repetitions)]
@@ -83,6 +104,26 @@ impl<T: FormField> ValidForm<T> {
     }
 }
 
+// Valid forms are serialized as lists of fields, so they can be sent later.
+
+impl<T: FormField + Serialize> Serialize for ValidForm<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.values())
+    }
+}
+
+impl<'de, T: FormField + Deserialize<'de>> Deserialize<'de> for ValidForm<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let fields = Vec::<T>::deserialize(deserializer)?;
+        Ok(Self(fields.into_iter().map(|field| (field.key(), field)).collect()))
+    }
+}
+
 // ------ Problem ------
 
 #[derive(Clone)]
@@ -1,7 +1,9 @@
 use crate::{
     coder::encoder::form::article_editor::ValidForm as ValidFormEncoder,
     entity::form::{self, FormField},
+    i18n,
 };
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
@@ -15,6 +17,17 @@ impl Default for Form {
     }
 }
 
+impl Form {
+    pub fn title(&self) -> &str {
+        self.iter_fields()
+            .find_map(|field| match field {
+                Field::Title(title) => Some(title.as_str()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
 // ------ ValidForm ------
 
 pub type ValidForm = form::ValidForm<Field>;
@@ -31,7 +44,7 @@ pub type Problem = form::Problem;
 
 // ------ Field ------
 
-#[derive(Clone, EnumIter)]
+#[derive(Clone, EnumIter, Serialize, Deserialize)]
 pub enum Field {
     Title(String),
     Description(String),
@@ -75,7 +88,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "title can't be blank",
+                        i18n::t("problem.title_blank"),
                     ))
                 } else {
                     None
@@ -85,7 +98,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "body can't be blank",
+                        i18n::t("problem.body_blank"),
                     ))
                 } else {
                     None
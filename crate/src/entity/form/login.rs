@@ -1,6 +1,7 @@
 use crate::{
     coder::encoder::form::login::ValidForm as ValidFormEncoder,
     entity::form::{self, FormField},
+    i18n,
 };
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -67,7 +68,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "email can't be blank",
+                        i18n::t("problem.email_blank"),
                     ))
                 } else {
                     None
@@ -77,7 +78,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "password can't be blank",
+                        i18n::t("problem.password_blank"),
                     ))
                 } else {
                     None
@@ -1,6 +1,7 @@
 use crate::{
     coder::encoder::form::register::ValidForm as ValidFormEncoder,
     entity::form::{self, FormField},
+    i18n,
 };
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -70,7 +71,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "username can't be blank",
+                        i18n::t("problem.username_blank"),
                     ))
                 } else {
                     None
@@ -80,7 +81,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "email can't be blank",
+                        i18n::t("problem.email_blank"),
                     ))
                 } else {
                     None
@@ -89,7 +90,7 @@ impl FormField for Field {
             Password(value) => match value.graphemes(true).count() {
                 0 => Some(form::Problem::new_invalid_field(
                     self.key(),
-                    "password can't be blank",
+                    i18n::t("problem.password_blank"),
                 )),
                 // @TODO: use exclusive range pattern once stabilized
                 // https://github.com/rust-lang/rust/issues/37854
@@ -98,9 +99,9 @@ impl FormField for Field {
                 {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        format!(
-                            "password is too short (minimum is {} characters)",
-                            form::MIN_PASSWORD_LENGTH
+                        i18n::t_with(
+                            "problem.password_too_short",
+                            &[("min", &form::MIN_PASSWORD_LENGTH)],
                         ),
                     ))
                 },
@@ -1,6 +1,7 @@
 use crate::{
     coder::encoder::form::settings::ValidForm as ValidFormEncoder,
     entity::form::{self, FormField},
+    i18n,
 };
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -77,7 +78,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "username can't be blank",
+                        i18n::t("problem.username_blank"),
                     ))
                 } else {
                     None
@@ -87,7 +88,7 @@ impl FormField for Field {
                 if value.is_empty() {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        "email can't be blank",
+                        i18n::t("problem.email_blank"),
                     ))
                 } else {
                     None
@@ -101,9 +102,9 @@ impl FormField for Field {
                 {
                     Some(form::Problem::new_invalid_field(
                         self.key(),
-                        format!(
-                            "password is too short (minimum is {} characters)",
-                            form::MIN_PASSWORD_LENGTH
+                        i18n::t_with(
+                            "problem.password_too_short",
+                            &[("min", &form::MIN_PASSWORD_LENGTH)],
                         ),
                     ))
                 },
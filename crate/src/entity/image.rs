@@ -1,10 +1,17 @@
+use crate::config;
+
 static IMAGES_BASE_URL: &str = "/static/images";
 
 pub struct Image(String);
 
 impl Image {
     pub fn new(filename: &str) -> Self {
-        Self(format!("{}/{}", IMAGES_BASE_URL, filename))
+        Self(format!(
+            "{}{}/{}",
+            config::current().base_path,
+            IMAGES_BASE_URL,
+            filename
+        ))
     }
 
     pub fn url(&self) -> &str {
@@ -1,4 +1,399 @@
+use crate::i18n;
+use highlight::Language;
 use newtype::NewType;
+use pulldown_cmark::{html, Event, Parser, Tag};
+use seed::prelude::*;
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+mod highlight;
+
+/// URL schemes allowed in links and images.
+/// URLs without a scheme (relative ones) are allowed too.
+const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+const WORDS_PER_MINUTE: usize = 200;
+/// Distinguishes heading ids from other ids on the page
+/// (e.g. "comment-42").
+const HEADING_ANCHOR_PREFIX: &str = "heading-";
+
+// ------ Markdown ------
 
 #[derive(NewType, Clone)]
 pub struct Markdown(String);
+
+impl Markdown {
+    pub fn headings(&self) -> Vec<Heading> {
+        headings(&safe_events(self))
+    }
+
+    pub fn word_count(&self) -> usize {
+        Parser::new(self)
+            .map(|event| match event {
+                Event::Text(text) | Event::Code(text) | Event::Html(text) => {
+                    text.unicode_words().count()
+                },
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Estimated reading time in minutes, at least one.
+    pub fn reading_time(&self) -> usize {
+        self.word_count().div_ceil(WORDS_PER_MINUTE).max(1)
+    }
+}
+
+// ------ Heading ------
+
+pub struct Heading {
+    pub level: u32,
+    pub text: String,
+    /// Id of the rendered heading element, unique in the document.
+    pub anchor: String,
+}
+
+/// Render Markdown to HTML that is safe to inject into the page.
+///
+/// Raw HTML in the input is escaped and links or images with unsafe URLs
+/// (e.g. `javascript:` or `data:`) are replaced by their text.
+/// Headings get ids from `Markdown::headings`
+/// and code blocks in known languages are highlighted.
+pub fn to_safe_html(markdown: &str) -> String {
+    let events = highlight_code_blocks(safe_events(markdown));
+    let mut anchors =
+        headings(&events).into_iter().map(|heading| heading.anchor);
+
+    let mut html_text = String::new();
+    html::push_html(
+        &mut html_text,
+        events.into_iter().map(|event| match event {
+            Event::Start(Tag::Heading(level)) => Event::Html(
+                format!(
+                    "<h{} id=\"{}\">",
+                    level,
+                    anchors.next().unwrap_or_default()
+                )
+                .into(),
+            ),
+            event => event,
+        }),
+    );
+    html_text
+}
+
+// ------ view markdown ------
+
+pub fn view<Ms>(markdown: &str) -> Vec<Node<Ms>> {
+    Node::from_html(&to_safe_html(markdown))
+}
+
+pub fn view_reading_time<Ms>(markdown: &Markdown) -> Node<Ms> {
+    span![
+        class!["date"],
+        i18n::t_with(
+            "markdown.reading_time",
+            &[("count", &markdown.reading_time())]
+        )
+    ]
+}
+
+// ====== PRIVATE ======
+
+fn safe_events(markdown: &str) -> Vec<Event<'_>> {
+    Parser::new(markdown)
+        .filter_map(|event| match event {
+            Event::Html(html) => Some(Event::Text(html)),
+            Event::Start(Tag::Link(_, url, _))
+            | Event::End(Tag::Link(_, url, _))
+            | Event::Start(Tag::Image(_, url, _))
+            | Event::End(Tag::Image(_, url, _))
+                if !is_safe_url(&url) =>
+            {
+                None
+            },
+            event => Some(event),
+        })
+        .collect()
+}
+
+fn highlight_code_blocks(events: Vec<Event>) -> Vec<Event> {
+    let mut highlighted_events = Vec::with_capacity(events.len());
+    // the language of the current code block and its code
+    let mut code_block: Option<(&Language, String)> = None;
+
+    for event in events {
+        match event {
+            Event::Start(Tag::CodeBlock(info)) => {
+                code_block = info
+                    .split(' ')
+                    .next()
+                    .and_then(Language::find)
+                    .map(|language| (language, String::new()));
+                highlighted_events.push(Event::Start(Tag::CodeBlock(info)));
+            },
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = &mut code_block {
+                    code.push_str(&text);
+                }
+            },
+            Event::End(Tag::CodeBlock(info)) => {
+                if let Some((language, code)) = code_block.take() {
+                    highlighted_events
+                        .push(Event::Html(language.highlight(&code).into()));
+                }
+                highlighted_events.push(Event::End(Tag::CodeBlock(info)));
+            },
+            event => highlighted_events.push(event),
+        }
+    }
+    highlighted_events
+}
+
+fn headings(events: &[Event]) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut current_heading: Option<(u32, String)> = None;
+    // including the deduplicated ones, e.g. "heading-intro-1"
+    let mut used_anchors = HashSet::new();
+
+    for event in events {
+        match (event, &mut current_heading) {
+            (Event::Start(Tag::Heading(level)), _) => {
+                current_heading = Some((*level, String::new()));
+            },
+            (Event::Text(text), Some((_, heading_text)))
+            | (Event::Code(text), Some((_, heading_text))) => {
+                heading_text.push_str(text);
+            },
+            (Event::End(Tag::Heading(_)), Some((level, text))) => {
+                headings.push(Heading {
+                    level: *level,
+                    text: text.trim().to_owned(),
+                    anchor: unique_anchor(new_anchor(text), &mut used_anchors),
+                });
+                current_heading = None;
+            },
+            _ => (),
+        }
+    }
+    headings
+}
+
+/// "Hello, World!" -> "heading-hello-world"
+fn new_anchor(text: &str) -> String {
+    let anchor = text
+        .unicode_words()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if anchor.is_empty() {
+        format!("{}section", HEADING_ANCHOR_PREFIX)
+    } else {
+        format!("{}{}", HEADING_ANCHOR_PREFIX, anchor)
+    }
+}
+
+/// "heading-intro" -> "heading-intro-1" if "heading-intro" is already used.
+fn unique_anchor(anchor: String, used_anchors: &mut HashSet<String>) -> String {
+    let mut unique = anchor.clone();
+    let mut suffix = 0;
+    while used_anchors.contains(&unique) {
+        suffix += 1;
+        unique = format!("{}-{}", anchor, suffix);
+    }
+    used_anchors.insert(unique.clone());
+    unique
+}
+
+fn is_safe_url(url: &str) -> bool {
+    // browsers ignore whitespace and control characters in the scheme
+    let url = url
+        .chars()
+        .filter(|character| {
+            !character.is_ascii_whitespace() && !character.is_control()
+        })
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    match url.find(|character| [':', '/', '?', '#'].contains(&character)) {
+        Some(index) if url[index..].starts_with(':') => {
+            SAFE_URL_SCHEMES.contains(&&url[..index])
+        },
+        _ => true,
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const MALICIOUS_INPUTS: &[&str] = &[
+        "<script>alert('xss')</script>",
+        "<SCRIPT SRC=https://example.com/xss.js></SCRIPT>",
+        "<img src=x onerror=alert('xss')>",
+        "<iframe src=\"javascript:alert('xss')\"></iframe>",
+        "<a href=\"javascript:alert('xss')\">click</a>",
+        "<div onmouseover=\"alert('xss')\">hover</div>",
+        "<svg onload=alert('xss')>",
+        "<style>body { display: none; }</style>",
+        "text <b onclick=alert('xss')>inline</b> html",
+        "[click](javascript:alert('xss'))",
+        "[click](JavaScript:alert('xss'))",
+        "[click](java\tscript:alert('xss'))",
+        "[click](&#106;avascript:alert('xss'))",
+        "[click](vbscript:msgbox('xss'))",
+        "[click](data:text/html;base64,PHNjcmlwdD5hbGVydCgneHNzJyk8L3NjcmlwdD4=)",
+        "![image](javascript:alert('xss'))",
+        "![image](data:image/svg+xml;base64,PHN2ZyBvbmxvYWQ9YWxlcnQoJ3hzcycpPg==)",
+        "[click][xss]\n\n[xss]: javascript:alert('xss')",
+        "<javascript:alert('xss')>",
+    ];
+
+    #[wasm_bindgen_test]
+    fn malicious_inputs_test() {
+        for input in MALICIOUS_INPUTS {
+            // ====== ACT ======
+            let html_text = to_safe_html(input).to_ascii_lowercase();
+
+            // ====== ASSERT ======
+            for forbidden in &[
+                "<script",
+                "<img src=\"x\"",
+                "<iframe",
+                "<div",
+                "<svg",
+                "<style",
+                "<b ",
+                "href=\"javascript",
+                "href=\"java",
+                "href=\"vbscript",
+                "href=\"data",
+                "src=\"javascript",
+                "src=\"data",
+            ] {
+                assert!(
+                    !html_text.contains(forbidden),
+                    "Input {:?} rendered as {:?}",
+                    input,
+                    html_text
+                );
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn raw_html_is_escaped_test() {
+        // ====== ACT ======
+        let html_text = to_safe_html("<script>alert('xss')</script>");
+
+        // ====== ASSERT ======
+        assert_eq!(html_text, "&lt;script&gt;alert('xss')&lt;/script&gt;");
+    }
+
+    #[wasm_bindgen_test]
+    fn unsafe_link_keeps_text_test() {
+        // ====== ACT ======
+        let html_text = to_safe_html("[click](javascript:alert('xss'))");
+
+        // ====== ASSERT ======
+        assert_eq!(html_text, "<p>click</p>\n");
+    }
+
+    #[wasm_bindgen_test]
+    fn safe_markdown_test() {
+        // ====== ACT ======
+        let html_text = to_safe_html(
+            "# Title\n\n[home](/) [site](https://example.com) \
+             ![logo](images/logo.png) [mail](mailto:john@example.com)",
+        );
+
+        // ====== ASSERT ======
+        assert_eq!(
+            html_text,
+            "<h1 id=\"heading-title\">Title</h1>\n<p><a href=\"/\">home</a> \
+             <a href=\"https://example.com\">site</a> \
+             <img src=\"images/logo.png\" alt=\"logo\" /> \
+             <a href=\"mailto:john@example.com\">mail</a></p>\n"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn headings_test() {
+        // ====== ARRANGE ======
+        let markdown = Markdown::from(
+            "# Intro\n\ntext\n\n## `Result` and *errors*\n\n## Intro\n\n\
+             ## Intro\n\n## Intro 1"
+                .to_owned(),
+        );
+
+        // ====== ACT ======
+        let headings = markdown.headings();
+
+        // ====== ASSERT ======
+        assert_eq!(
+            headings
+                .iter()
+                .map(|heading| (heading.level, heading.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "Intro"),
+                (2, "Result and errors"),
+                (2, "Intro"),
+                (2, "Intro"),
+                (2, "Intro 1")
+            ]
+        );
+        assert_eq!(
+            headings
+                .iter()
+                .map(|heading| heading.anchor.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "heading-intro",
+                "heading-result-and-errors",
+                "heading-intro-1",
+                "heading-intro-2",
+                "heading-intro-1-1"
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn reading_time_test() {
+        // ====== ARRANGE ======
+        let markdown = Markdown::from(format!(
+            "# Title\n\n{}\n\n`code`",
+            vec!["word"; 398].join(" ")
+        ));
+
+        // ====== ACT ======
+        let word_count = markdown.word_count();
+        let reading_time = markdown.reading_time();
+
+        // ====== ASSERT ======
+        assert_eq!(word_count, 400);
+        assert_eq!(reading_time, 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn highlighted_code_block_test() {
+        // ====== ACT ======
+        let html_text = to_safe_html(
+            "```rust\nlet x = \"<b>\";\n```\n\n```unknown\n<b>\n```",
+        );
+
+        // ====== ASSERT ======
+        assert_eq!(
+            html_text,
+            "<pre><code class=\"language-rust\">\
+             <span style=\"color: #d73a49\">let</span> x = \
+             <span style=\"color: #032f62\">&quot;&lt;b&gt;&quot;</span>;\n\
+             </code></pre>\n\
+             <pre><code class=\"language-unknown\">&lt;b&gt;\n</code></pre>\n"
+        );
+    }
+}
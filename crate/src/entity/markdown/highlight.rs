@@ -0,0 +1,472 @@
+// ------ Language ------
+
+/// Syntax highlighting rules for fenced code blocks.
+///
+/// It's a simple lexer - it recognizes comments, strings, numbers, keywords,
+/// literals and shell variables; everything else is plain text.
+pub struct Language {
+    names: &'static [&'static str],
+    keywords: &'static [&'static str],
+    literals: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Single quotes delimit only char literals like `'a'` or `'\n'`,
+    /// otherwise they are lifetimes.
+    char_literals: bool,
+    variable_prefix: Option<char>,
+    case_sensitive: bool,
+}
+
+const LANGUAGES: &[Language] = &[
+    Language {
+        names: &["rust", "rs"],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate",
+            "dyn", "else", "enum", "extern", "fn", "for", "if", "impl", "in",
+            "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+            "return", "self", "Self", "static", "struct", "super", "trait",
+            "type", "unsafe", "use", "where", "while",
+        ],
+        literals: &["true", "false", "None", "Some", "Ok", "Err"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        char_literals: true,
+        variable_prefix: None,
+        case_sensitive: true,
+    },
+    Language {
+        names: &["typescript", "ts", "tsx", "javascript", "js", "jsx"],
+        keywords: &[
+            "abstract",
+            "as",
+            "async",
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "finally",
+            "for",
+            "from",
+            "function",
+            "if",
+            "implements",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "let",
+            "new",
+            "of",
+            "private",
+            "protected",
+            "public",
+            "readonly",
+            "return",
+            "static",
+            "switch",
+            "this",
+            "throw",
+            "try",
+            "type",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "yield",
+        ],
+        literals: &["true", "false", "null", "undefined"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        char_literals: false,
+        variable_prefix: None,
+        case_sensitive: true,
+    },
+    Language {
+        names: &["json"],
+        keywords: &[],
+        literals: &["true", "false", "null"],
+        line_comments: &[],
+        block_comment: None,
+        quotes: &['"'],
+        char_literals: false,
+        variable_prefix: None,
+        case_sensitive: true,
+    },
+    Language {
+        names: &["toml"],
+        keywords: &[],
+        literals: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        char_literals: false,
+        variable_prefix: None,
+        case_sensitive: true,
+    },
+    Language {
+        names: &["shell", "sh", "bash", "zsh"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi",
+            "for", "function", "if", "in", "local", "return", "then", "until",
+            "while",
+        ],
+        literals: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        char_literals: false,
+        variable_prefix: Some('$'),
+        case_sensitive: true,
+    },
+    Language {
+        names: &["sql"],
+        keywords: &[
+            "add",
+            "all",
+            "alter",
+            "and",
+            "as",
+            "asc",
+            "begin",
+            "between",
+            "by",
+            "case",
+            "commit",
+            "create",
+            "delete",
+            "desc",
+            "distinct",
+            "drop",
+            "else",
+            "end",
+            "exists",
+            "foreign",
+            "from",
+            "group",
+            "having",
+            "in",
+            "index",
+            "inner",
+            "insert",
+            "into",
+            "is",
+            "join",
+            "key",
+            "left",
+            "like",
+            "limit",
+            "not",
+            "on",
+            "or",
+            "order",
+            "outer",
+            "primary",
+            "references",
+            "right",
+            "rollback",
+            "select",
+            "set",
+            "table",
+            "then",
+            "union",
+            "unique",
+            "update",
+            "values",
+            "when",
+            "where",
+            "with",
+        ],
+        literals: &["true", "false", "null"],
+        line_comments: &["--"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['\'', '"'],
+        char_literals: false,
+        variable_prefix: None,
+        case_sensitive: false,
+    },
+];
+
+impl Language {
+    /// Find the language by the name from the code block info string.
+    pub fn find(name: &str) -> Option<&'static Self> {
+        let name = name.to_ascii_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|language| language.names.contains(&name.as_str()))
+    }
+
+    /// Highlighted and HTML-escaped `code`.
+    pub fn highlight(&self, code: &str) -> String {
+        let mut html_text = String::new();
+        let mut rest = code;
+        while let Some(character) = rest.chars().next() {
+            let (kind, length) = self.next_token(rest, character);
+            let (token, new_rest) = rest.split_at(length);
+            push_token(&mut html_text, kind, token);
+            rest = new_rest;
+        }
+        html_text
+    }
+
+    // ====== PRIVATE ======
+
+    /// Kind and byte length of the token at the start of `code`.
+    fn next_token(&self, code: &str, first: char) -> (TokenKind, usize) {
+        if self.line_comments.iter().any(|marker| code.starts_with(marker)) {
+            return (TokenKind::Comment, code.find('\n').unwrap_or(code.len()));
+        }
+        if let Some((start, end)) = self.block_comment {
+            if let Some(rest) = code.strip_prefix(start) {
+                let length = rest.find(end).map_or(code.len(), |index| {
+                    start.len() + index + end.len()
+                });
+                return (TokenKind::Comment, length);
+            }
+        }
+        if self.quotes.contains(&first) {
+            if let Some(length) = self.string_length(code, first) {
+                return (TokenKind::String, length);
+            }
+        }
+        if first.is_ascii_digit() {
+            return (TokenKind::Number, word_length(code));
+        }
+        if is_word_start(first) {
+            let length = word_length(code);
+            return (self.word_kind(&code[..length]), length);
+        }
+        if Some(first) == self.variable_prefix {
+            if let Some(length) = variable_length(code) {
+                return (TokenKind::Variable, length);
+            }
+        }
+        (TokenKind::Plain, first.len_utf8())
+    }
+
+    fn string_length(&self, code: &str, quote: char) -> Option<usize> {
+        let mut escaped = false;
+        for (index, character) in code.char_indices().skip(1) {
+            if self.char_literals && quote == '\'' && index > 3 {
+                return None;
+            }
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if character == quote => {
+                    return Some(index + character.len_utf8())
+                },
+                _ => (),
+            }
+        }
+        if self.char_literals && quote == '\'' {
+            None
+        } else {
+            Some(code.len())
+        }
+    }
+
+    fn word_kind(&self, word: &str) -> TokenKind {
+        let contains = |words: &[&str]| {
+            words.iter().any(|candidate| {
+                if self.case_sensitive {
+                    *candidate == word
+                } else {
+                    candidate.eq_ignore_ascii_case(word)
+                }
+            })
+        };
+        if contains(self.keywords) {
+            TokenKind::Keyword
+        } else if contains(self.literals) {
+            TokenKind::Literal
+        } else {
+            TokenKind::Plain
+        }
+    }
+}
+
+// ------ TokenKind ------
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Plain,
+    Comment,
+    String,
+    Number,
+    Keyword,
+    Literal,
+    Variable,
+}
+
+impl TokenKind {
+    const fn color(self) -> Option<&'static str> {
+        match self {
+            Self::Plain => None,
+            Self::Comment => Some("#6a737d"),
+            Self::String => Some("#032f62"),
+            Self::Number | Self::Literal => Some("#005cc5"),
+            Self::Keyword => Some("#d73a49"),
+            Self::Variable => Some("#e36209"),
+        }
+    }
+}
+
+// ====== PRIVATE ======
+
+fn push_token(html_text: &mut String, kind: TokenKind, token: &str) {
+    match kind.color() {
+        Some(color) => {
+            html_text.push_str("<span style=\"color: ");
+            html_text.push_str(color);
+            html_text.push_str("\">");
+            push_escaped(html_text, token);
+            html_text.push_str("</span>");
+        },
+        None => push_escaped(html_text, token),
+    }
+}
+
+fn push_escaped(html_text: &mut String, text: &str) {
+    for character in text.chars() {
+        match character {
+            '&' => html_text.push_str("&amp;"),
+            '<' => html_text.push_str("&lt;"),
+            '>' => html_text.push_str("&gt;"),
+            '"' => html_text.push_str("&quot;"),
+            _ => html_text.push(character),
+        }
+    }
+}
+
+fn is_word_start(character: char) -> bool {
+    character.is_alphabetic() || character == '_'
+}
+
+fn is_word_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Length of identifiers and numbers like `1.5e3` or `0xff_u8`.
+fn word_length(code: &str) -> usize {
+    code.char_indices()
+        .find(|(index, character)| {
+            let in_number = *character == '.'
+                && code[..*index]
+                    .starts_with(|first: char| first.is_ascii_digit())
+                && code[index + 1..]
+                    .starts_with(|next: char| next.is_ascii_digit());
+            !is_word_char(*character) && !in_number
+        })
+        .map_or(code.len(), |(index, _)| index)
+}
+
+/// Length of shell variables like `$HOME`, `${HOME}` or `$1`.
+fn variable_length(code: &str) -> Option<usize> {
+    let name = &code[1..];
+    if name.starts_with('{') {
+        name.find('}').map(|index| index + 2)
+    } else {
+        match word_length(name) {
+            0 => None,
+            length => Some(length + 1),
+        }
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn span(color: &str, text: &str) -> String {
+        format!("<span style=\"color: {}\">{}</span>", color, text)
+    }
+
+    #[wasm_bindgen_test]
+    fn rust_test() {
+        // ====== ARRANGE ======
+        let language = Language::find("Rust").unwrap();
+
+        // ====== ACT ======
+        let html_text =
+            language.highlight("fn f<'a>() -> char { 'x' } // <done>");
+
+        // ====== ASSERT ======
+        assert_eq!(
+            html_text,
+            format!(
+                "{} f&lt;'a&gt;() -&gt; char {{ {} }} {}",
+                span("#d73a49", "fn"),
+                span("#032f62", "'x'"),
+                span("#6a737d", "// &lt;done&gt;"),
+            )
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn sql_test() {
+        // ====== ARRANGE ======
+        let language = Language::find("sql").unwrap();
+
+        // ====== ACT ======
+        let html_text = language.highlight("SELECT 1.5 -- \"x\"\nfrom t");
+
+        // ====== ASSERT ======
+        assert_eq!(
+            html_text,
+            format!(
+                "{} {} {}\n{} t",
+                span("#d73a49", "SELECT"),
+                span("#005cc5", "1.5"),
+                span("#6a737d", "-- &quot;x&quot;"),
+                span("#d73a49", "from"),
+            )
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn shell_test() {
+        // ====== ARRANGE ======
+        let language = Language::find("bash").unwrap();
+
+        // ====== ACT ======
+        let html_text = language.highlight("echo \"$HOME\" ${PATH} # x");
+
+        // ====== ASSERT ======
+        assert_eq!(
+            html_text,
+            format!(
+                "echo {} {} {}",
+                span("#032f62", "&quot;$HOME&quot;"),
+                span("#e36209", "${PATH}"),
+                span("#6a737d", "# x"),
+            )
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unknown_language_test() {
+        // ====== ACT ======
+        let language = Language::find("brainfuck");
+
+        // ====== ASSERT ======
+        assert!(language.is_none());
+    }
+}
@@ -6,6 +6,9 @@ pub use article::{
     Article,
 };
 
+pub mod api_error;
+pub use api_error::ApiError;
+
 pub mod image;
 pub use image::Image;
 
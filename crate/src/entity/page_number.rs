@@ -1,6 +1,6 @@
 use newtype::NewType;
 
-#[derive(NewType, Clone, Copy, PartialEq, Eq)]
+#[derive(NewType, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PageNumber(usize);
 
 impl Default for PageNumber {
@@ -12,6 +12,14 @@ impl<T> PaginatedList<T> {
     pub fn total_pages(&self) -> usize {
         div_ceil(self.total, self.per_page.get())
     }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedList<U> {
+        PaginatedList {
+            items: self.items.into_iter().map(f).collect(),
+            per_page: self.per_page,
+            total: self.total,
+        }
+    }
 }
 
 impl<T> Default for PaginatedList<T> {
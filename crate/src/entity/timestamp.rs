@@ -1,9 +1,12 @@
-use crate::entity::ErrorMessage;
-use chrono::prelude::*;
+use crate::{entity::ErrorMessage, i18n};
+use chrono::{prelude::*, Duration};
+use gloo_timers::future::TimeoutFuture;
 use newtype::NewType;
 use seed::prelude::*;
 use std::convert::TryFrom;
 
+const TICK_INTERVAL_MS: u32 = 60_000;
+
 // ------ Timestamp ------
 
 #[derive(NewType, Clone)]
@@ -20,16 +23,111 @@ impl TryFrom<String> for Timestamp {
     }
 }
 
+impl Timestamp {
+    /// "just now", "3 hours ago", "yesterday" or the date for older ones.
+    pub fn to_relative_string(&self, now: DateTime<Local>) -> String {
+        let elapsed = now.signed_duration_since(self.0);
+        let yesterday = now.naive_local().date() - Duration::days(1);
+
+        if elapsed < Duration::minutes(1) {
+            i18n::t("time.just_now").into()
+        } else if elapsed < Duration::hours(1) {
+            i18n::t_count("time.minutes_ago", elapsed.num_minutes())
+        } else if elapsed < Duration::days(1)
+            && self.naive_local().date() != yesterday
+        {
+            i18n::t_count("time.hours_ago", elapsed.num_hours())
+        } else if self.naive_local().date() == yesterday {
+            i18n::t("time.yesterday").into()
+        } else if elapsed < Duration::weeks(1) {
+            i18n::t_count("time.days_ago", elapsed.num_days().max(2))
+        } else {
+            self.to_date_string()
+        }
+    }
+
+    /// "February 14, 2018" in the current language.
+    pub fn to_date_string(&self) -> String {
+        self.to_js_date()
+            .to_locale_date_string(
+                i18n::current().tag(),
+                &date_format_options(&[
+                    ("year", "numeric"),
+                    ("month", "long"),
+                    ("day", "numeric"),
+                ]),
+            )
+            .into()
+    }
+
+    /// "February 14, 2018, 8:21 AM" in the current language.
+    pub fn to_date_time_string(&self) -> String {
+        self.to_js_date()
+            .to_locale_string(
+                i18n::current().tag(),
+                &date_format_options(&[
+                    ("year", "numeric"),
+                    ("month", "long"),
+                    ("day", "numeric"),
+                    ("hour", "numeric"),
+                    ("minute", "2-digit"),
+                ]),
+            )
+            .into()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn to_js_date(&self) -> js_sys::Date {
+        js_sys::Date::new(&JsValue::from_f64(self.timestamp_millis() as f64))
+    }
+}
+
+/// Relative timestamps are re-rendered on every tick.
+pub async fn notify_on_tick<Ms>(msg: Ms) -> Result<Ms, Ms> {
+    TimeoutFuture::new(TICK_INTERVAL_MS).await;
+    Ok(msg)
+}
+
 // ------ view timestamp ------
 
 pub fn view<Ms>(timestamp: &Timestamp) -> Node<Ms> {
     span![
         class!["date"],
-        // "February 14, 2018"
-        timestamp.format("%B %-d, %-Y").to_string()
+        attrs! {At::Title => timestamp.to_date_time_string()},
+        timestamp.to_relative_string(Local::now())
     ]
 }
 
+/// The "edited" marker, it's empty when the entity hasn't been updated.
+pub fn view_edited<Ms>(
+    created_at: &Timestamp,
+    updated_at: &Timestamp,
+) -> Node<Ms> {
+    if updated_at.signed_duration_since(created_at.0) < Duration::seconds(1) {
+        return empty![];
+    }
+    span![
+        attrs! {
+            At::Title => i18n::t_with(
+                "time.edited_at",
+                &[("date", &updated_at.to_date_time_string())]
+            )
+        },
+        format!(" {}", i18n::t("time.edited"))
+    ]
+}
+
+// ====== PRIVATE ======
+
+fn date_format_options(options: &[(&str, &str)]) -> JsValue {
+    let object = js_sys::Object::new();
+    for (key, value) in options {
+        js_sys::Reflect::set(&object, &(*key).into(), &(*value).into())
+            .expect("set date format option");
+    }
+    object.into()
+}
+
 // ====== ====== TESTS ====== ======
 
 #[cfg(test)]
@@ -54,4 +152,22 @@ pub mod tests {
         // ====== ASSERT ======
         assert_eq!(node.get_text(), "July 24, 2019");
     }
+
+    #[wasm_bindgen_test]
+    fn relative_string_test() {
+        // ====== ARRANGE ======
+        let now = Local.ymd(2019, 7, 24).and_hms(18, 30, 0);
+        let relative_string = |elapsed: Duration| {
+            Timestamp::from(now - elapsed).to_relative_string(now)
+        };
+
+        // ====== ACT & ASSERT ======
+        assert_eq!(relative_string(Duration::seconds(30)), "just now");
+        assert_eq!(relative_string(Duration::minutes(1)), "1 minute ago");
+        assert_eq!(relative_string(Duration::minutes(59)), "59 minutes ago");
+        assert_eq!(relative_string(Duration::hours(3)), "3 hours ago");
+        assert_eq!(relative_string(Duration::hours(20)), "yesterday");
+        assert_eq!(relative_string(Duration::days(1)), "yesterday");
+        assert_eq!(relative_string(Duration::days(3)), "3 days ago");
+    }
 }
@@ -3,7 +3,15 @@ use shrinkwraprs::Shrinkwrap;
 use std::borrow::{Borrow, Cow};
 
 #[derive(
-    Shrinkwrap, Eq, PartialEq, Clone, Debug, Deserialize, Serialize, Default,
+    Shrinkwrap,
+    Eq,
+    PartialEq,
+    Hash,
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    Default,
 )]
 pub struct Username<'a>(Cow<'a, str>);
 
@@ -17,6 +25,12 @@ impl<'a> Username<'a> {
     }
 }
 
+impl<'a> Borrow<str> for Username<'a> {
+    fn borrow(&self) -> &str {
+        self.0.borrow()
+    }
+}
+
 impl<'a, T> From<T> for Username<'a>
 where
     T: Into<Cow<'a, str>>,
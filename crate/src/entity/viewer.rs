@@ -1,6 +1,6 @@
 use crate::{
     entity::{Avatar, Profile, Username},
-    storage,
+    storage::{self, StorageError},
 };
 use serde::{Deserialize, Serialize};
 
@@ -19,7 +19,7 @@ impl Viewer {
         &self.profile.avatar
     }
 
-    pub fn store(&self) {
-        storage::store_viewer(self);
+    pub fn store(&self) -> Result<(), StorageError> {
+        storage::store_viewer(self)
     }
 }
@@ -0,0 +1,283 @@
+use crate::{logger, storage};
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, fmt::Display};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+mod de;
+mod en;
+
+thread_local! {
+    static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+/// Load the stored language or the browser one and make it `current`.
+/// It should be called once, before the first render.
+pub fn init() {
+    let language = storage::load_language()
+        .unwrap_or_else(|error| {
+            logger::error(error);
+            None
+        })
+        .or_else(|| {
+            seed::window()
+                .navigator()
+                .language()
+                .and_then(|tag| Language::from_tag(&tag))
+        })
+        .unwrap_or_default();
+    set_current(language);
+}
+
+pub fn current() -> Language {
+    LANGUAGE.with(Cell::get)
+}
+
+pub fn set_current(language: Language) {
+    LANGUAGE.with(|current| current.set(language));
+}
+
+// ------ Language ------
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter,
+)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// BCP 47 language tag.
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// Name of the language in the language itself.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    /// "de-AT" => `German`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary_tag = tag.split('-').next()?.to_ascii_lowercase();
+        Self::iter().find(|language| language.tag() == primary_tag)
+    }
+
+    const fn catalog(self) -> Catalog {
+        match self {
+            Self::English => en::CATALOG,
+            Self::German => de::CATALOG,
+        }
+    }
+
+    const fn plural_form(self, count: i64) -> PluralForm {
+        match self {
+            Self::English | Self::German => {
+                if count == 1 {
+                    PluralForm::One
+                } else {
+                    PluralForm::Other
+                }
+            },
+        }
+    }
+}
+
+// ------ Message ------
+
+/// Catalog entry; `{name}` placeholders are replaced by arguments.
+pub enum Message {
+    Text(&'static str),
+    /// Forms for a `{count}`.
+    Plural {
+        one: &'static str,
+        other: &'static str,
+    },
+}
+
+type Catalog = &'static [(&'static str, Message)];
+
+enum PluralForm {
+    One,
+    Other,
+}
+
+// ------ translate ------
+
+/// Message for the `key` in the current language.
+pub fn t(key: &'static str) -> &'static str {
+    match message(key) {
+        Some(Message::Text(text)) => text,
+        Some(Message::Plural {
+            other,
+            ..
+        }) => other,
+        None => key,
+    }
+}
+
+/// Message with replaced placeholders, e.g. `("name", &"John")` for `{name}`.
+pub fn t_with(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    replace_placeholders(t(key), args)
+}
+
+/// Message in the plural form for the `count`.
+pub fn t_count(key: &'static str, count: i64) -> String {
+    let language = current();
+    let text = match message(key) {
+        Some(Message::Plural {
+            one,
+            other,
+        }) => match language.plural_form(count) {
+            PluralForm::One => one,
+            PluralForm::Other => other,
+        },
+        Some(Message::Text(text)) => text,
+        None => key,
+    };
+    replace_placeholders(text, &[("count", &count)])
+}
+
+// ------ view functions ------
+
+pub fn view_language_picker<Ms: 'static>(
+    on_select: impl FnOnce(Language) -> Ms + Clone + 'static,
+) -> Node<Ms> {
+    let current = current();
+    select![
+        attrs! {At::Title => t("footer.language")},
+        input_ev(Ev::Change, move |tag| {
+            on_select(Language::from_tag(&tag).unwrap_or(current))
+        }),
+        Language::iter().map(|language| {
+            option![
+                attrs! {
+                    At::Value => language.tag(),
+                    At::Selected => (language == current).as_at_value()
+                },
+                language.name()
+            ]
+        })
+    ]
+}
+
+// ====== PRIVATE ======
+
+fn message(key: &str) -> Option<&'static Message> {
+    let find = |language: Language| {
+        language
+            .catalog()
+            .iter()
+            .find(|(catalog_key, _)| *catalog_key == key)
+            .map(|(_, message)| message)
+    };
+    let language = current();
+    find(language).or_else(|| {
+        logger::error(format!(
+            "Message '{}' is missing in {:?} catalog",
+            key, language
+        ));
+        find(Language::default())
+    })
+}
+
+fn replace_placeholders(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(text.to_owned(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Keys, message kinds and placeholders of the `language` catalog.
+    fn catalog_shape(language: Language) -> BTreeSet<String> {
+        language
+            .catalog()
+            .iter()
+            .map(|(key, message)| {
+                let texts = match message {
+                    Message::Text(text) => vec![*text],
+                    Message::Plural {
+                        one,
+                        other,
+                    } => vec![*one, *other],
+                };
+                let placeholders = texts
+                    .iter()
+                    .flat_map(|text| text.split('{').skip(1))
+                    .filter_map(|part| part.split('}').next())
+                    .collect::<BTreeSet<_>>();
+                format!(
+                    "{} {} {:?}",
+                    key,
+                    texts.len(),
+                    placeholders.into_iter().collect::<Vec<_>>()
+                )
+            })
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn every_key_is_in_every_catalog_test() {
+        // ====== ARRANGE ======
+        let english_shape = catalog_shape(Language::English);
+
+        for language in Language::iter() {
+            // ====== ACT ======
+            let shape = catalog_shape(language);
+
+            // ====== ASSERT ======
+            assert_eq!(
+                shape.len(),
+                language.catalog().len(),
+                "{:?} catalog contains duplicate keys",
+                language
+            );
+            assert_eq!(
+                shape.symmetric_difference(&english_shape).collect::<Vec<_>>(),
+                Vec::<&String>::new(),
+                "{:?} catalog doesn't match English catalog",
+                language
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn t_count_test() {
+        // ====== ARRANGE ======
+        set_current(Language::English);
+
+        // ====== ACT ======
+        let one = t_count("time.minutes_ago", 1);
+        let other = t_count("time.minutes_ago", 5);
+
+        // ====== ASSERT ======
+        assert_eq!(one, "1 minute ago");
+        assert_eq!(other, "5 minutes ago");
+    }
+
+    #[wasm_bindgen_test]
+    fn from_tag_test() {
+        // ====== ACT & ASSERT ======
+        assert_eq!(Language::from_tag("de-AT"), Some(Language::German));
+        assert_eq!(Language::from_tag("EN"), Some(Language::English));
+        assert_eq!(Language::from_tag("cs"), None);
+    }
+}
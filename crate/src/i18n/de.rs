@@ -0,0 +1,175 @@
+use super::{Catalog, Message::*};
+
+pub const CATALOG: Catalog = &[
+    // -- errors --
+    ("errors.dismiss", Text("OK")),
+    // -- nav --
+    ("nav.home", Text("Startseite")),
+    ("nav.new_article", Text("Neuer Beitrag")),
+    ("nav.drafts", Text("Entwürfe")),
+    ("nav.settings", Text("Einstellungen")),
+    ("nav.add_account", Text("Konto hinzufügen")),
+    // -- auth --
+    ("auth.sign_in", Text("Anmelden")),
+    ("auth.sign_up", Text("Registrieren")),
+    ("auth.sign_out", Text("Abmelden")),
+    // -- footer --
+    ("footer.attribution_prefix", Text("Ein interaktives Lernprojekt von ")),
+    (
+        "footer.attribution_suffix",
+        Text(". Code & Design lizenziert unter MIT."),
+    ),
+    ("footer.language", Text("Sprache")),
+    // -- loading --
+    (
+        "loading.article_failed",
+        Text("Der Artikel konnte nicht geladen werden."),
+    ),
+    (
+        "loading.comments_failed",
+        Text("Die Kommentare konnten nicht geladen werden."),
+    ),
+    ("loading.profile_failed", Text("Das Profil konnte nicht geladen werden.")),
+    ("loading.tags_failed", Text("Die Tags konnten nicht geladen werden.")),
+    // -- home --
+    ("home.tagline", Text("Ein Ort, um dein Wissen zu teilen.")),
+    ("home.your_feed", Text("Dein Feed")),
+    ("home.global_feed", Text("Globaler Feed")),
+    ("home.popular_tags", Text("Beliebte Tags")),
+    // -- profile --
+    ("profile.title", Text("Profil")),
+    ("profile.my_title", Text("Mein Profil")),
+    ("profile.title_for", Text("Profil - {username}")),
+    ("profile.my_articles", Text("Meine Artikel")),
+    ("profile.favorited_articles", Text("Favorisierte Artikel")),
+    // -- toast --
+    ("toast.follow_failed", Text("Folgen konnte nicht geändert werden")),
+    ("toast.favorite_failed", Text("Favorit konnte nicht geändert werden")),
+    ("toast.comment_failed", Text("Der Kommentar wurde nicht veröffentlicht")),
+    ("toast.rejected", Text("{change} wurde abgelehnt")),
+    // -- article --
+    ("article.title", Text("Artikel")),
+    ("article.contents", Text("Inhalt")),
+    ("article.or", Text(" oder ")),
+    ("article.to_comment", Text(", um zu kommentieren.")),
+    ("article.comment_placeholder", Text("Schreibe einen Kommentar...")),
+    ("article.post_comment", Text("Kommentar senden")),
+    ("article.favorite", Text("Artikel favorisieren ({count})")),
+    ("article.copy_link", Text("Link zum Kommentar kopieren")),
+    ("article.link_copied", Text("Link kopiert")),
+    ("article.edit", Text("Artikel bearbeiten")),
+    ("article.delete", Text("Artikel löschen")),
+    // -- editor --
+    ("editor.title_edit", Text("Artikel bearbeiten - {slug}")),
+    ("editor.title_new", Text("Neuer Artikel")),
+    ("editor.sign_in", Text("Melde dich an, um diesen Artikel zu bearbeiten.")),
+    (
+        "editor.draft_restored",
+        Text("Dein ungespeicherter Entwurf wurde wiederhergestellt. "),
+    ),
+    ("editor.all_drafts", Text("Alle Entwürfe")),
+    ("editor.publish", Text("Artikel veröffentlichen")),
+    ("editor.update", Text("Artikel aktualisieren")),
+    ("editor.title_placeholder", Text("Titel des Artikels")),
+    (
+        "editor.description_placeholder",
+        Text("Worum geht es in diesem Artikel?"),
+    ),
+    ("editor.body_placeholder", Text("Schreibe deinen Artikel (in Markdown)")),
+    ("editor.tags_placeholder", Text("Tags eingeben")),
+    ("editor.nothing_to_preview", Text("Nichts zum Anzeigen.")),
+    ("editor.write", Text("Schreiben")),
+    ("editor.preview", Text("Vorschau")),
+    // -- drafts --
+    ("drafts.empty", Text("Keine ungespeicherten Entwürfe.")),
+    ("drafts.untitled", Text("Ohne Titel")),
+    (
+        "drafts.published_changes",
+        Text("Änderungen an einem veröffentlichten Artikel"),
+    ),
+    ("drafts.new_article", Text("Neuer Artikel")),
+    ("drafts.discard", Text("Verwerfen")),
+    // -- login --
+    ("login.title", Text("Anmeldung")),
+    ("login.need_account", Text("Noch kein Konto?")),
+    // -- register --
+    ("register.title", Text("Registrierung")),
+    ("register.have_account", Text("Schon ein Konto?")),
+    // -- settings --
+    (
+        "settings.sign_in",
+        Text("Melde dich an, um deine Einstellungen zu sehen."),
+    ),
+    ("settings.update", Text("Einstellungen speichern")),
+    // -- form --
+    ("form.avatar", Text("URL des Profilbilds")),
+    ("form.username", Text("Dein Name")),
+    ("form.bio", Text("Kurz über dich")),
+    ("form.email", Text("E-Mail")),
+    ("form.password", Text("Passwort")),
+    // -- not_found --
+    ("not_found.title", Text("Seite nicht gefunden")),
+    ("not_found.heading", Text("Nicht gefunden")),
+    // -- feed --
+    ("feed.read_more", Text("Weiterlesen...")),
+    ("feed.empty", Text("Hier gibt es noch keine Artikel.")),
+    // -- author --
+    ("author.follow", Text("{username} folgen")),
+    ("author.unfollow", Text("{username} nicht mehr folgen")),
+    // -- outbox --
+    ("outbox.favorite", Text("„{slug}“ favorisieren")),
+    ("outbox.unfavorite", Text("„{slug}“ nicht mehr favorisieren")),
+    ("outbox.follow", Text("{username} folgen")),
+    ("outbox.unfollow", Text("{username} nicht mehr folgen")),
+    ("outbox.comment", Text("Kommentar zu „{slug}“")),
+    ("outbox.new_article", Text("Neuer Artikel")),
+    ("outbox.article_changes", Text("Änderungen an „{slug}“")),
+    ("outbox.sending", Text("Ausstehende Änderungen werden gesendet...")),
+    ("outbox.waiting", Text("Ausstehende Änderungen - warte auf Verbindung")),
+    // -- error --
+    ("error.network", Text("Keine Verbindung zum Server")),
+    ("error.timeout", Text("Der Server hat nicht rechtzeitig geantwortet")),
+    ("error.unauthorized", Text("Du musst dich anmelden")),
+    ("error.forbidden", Text("Das darfst du nicht")),
+    ("error.not_found", Text("Nicht gefunden")),
+    ("error.status", Text("Serverfehler ({code})")),
+    ("error.decode", Text("Datenfehler bei `{path}`")),
+    // -- problem --
+    ("problem.username_blank", Text("Der Name darf nicht leer sein")),
+    ("problem.email_blank", Text("Die E-Mail darf nicht leer sein")),
+    ("problem.password_blank", Text("Das Passwort darf nicht leer sein")),
+    ("problem.title_blank", Text("Der Titel darf nicht leer sein")),
+    ("problem.body_blank", Text("Der Text darf nicht leer sein")),
+    (
+        "problem.password_too_short",
+        Text("Das Passwort ist zu kurz (mindestens {min} Zeichen)"),
+    ),
+    // -- markdown --
+    ("markdown.reading_time", Text("{count} Min. Lesezeit")),
+    // -- time --
+    ("time.just_now", Text("gerade eben")),
+    (
+        "time.minutes_ago",
+        Plural {
+            one: "vor {count} Minute",
+            other: "vor {count} Minuten",
+        },
+    ),
+    (
+        "time.hours_ago",
+        Plural {
+            one: "vor {count} Stunde",
+            other: "vor {count} Stunden",
+        },
+    ),
+    ("time.yesterday", Text("gestern")),
+    (
+        "time.days_ago",
+        Plural {
+            one: "vor {count} Tag",
+            other: "vor {count} Tagen",
+        },
+    ),
+    ("time.edited_at", Text("Bearbeitet {date}")),
+    ("time.edited", Text("(bearbeitet)")),
+];
@@ -0,0 +1,157 @@
+use super::{Catalog, Message::*};
+
+pub const CATALOG: Catalog = &[
+    // -- errors --
+    ("errors.dismiss", Text("Ok")),
+    // -- nav --
+    ("nav.home", Text("Home")),
+    ("nav.new_article", Text("New Post")),
+    ("nav.drafts", Text("Drafts")),
+    ("nav.settings", Text("Settings")),
+    ("nav.add_account", Text("Add account")),
+    // -- auth --
+    ("auth.sign_in", Text("Sign in")),
+    ("auth.sign_up", Text("Sign up")),
+    ("auth.sign_out", Text("Sign out")),
+    // -- footer --
+    (
+        "footer.attribution_prefix",
+        Text("An interactive learning project from "),
+    ),
+    ("footer.attribution_suffix", Text(". Code & design licensed under MIT.")),
+    ("footer.language", Text("Language")),
+    // -- loading --
+    ("loading.article_failed", Text("Error loading article.")),
+    ("loading.comments_failed", Text("Error loading comments.")),
+    ("loading.profile_failed", Text("Error loading profile.")),
+    ("loading.tags_failed", Text("Error loading tags.")),
+    // -- home --
+    ("home.tagline", Text("A place to share your knowledge.")),
+    ("home.your_feed", Text("Your Feed")),
+    ("home.global_feed", Text("Global Feed")),
+    ("home.popular_tags", Text("Popular Tags")),
+    // -- profile --
+    ("profile.title", Text("Profile")),
+    ("profile.my_title", Text("My Profile")),
+    ("profile.title_for", Text("Profile - {username}")),
+    ("profile.my_articles", Text("My Articles")),
+    ("profile.favorited_articles", Text("Favorited Articles")),
+    // -- toast --
+    ("toast.follow_failed", Text("Follow change failed")),
+    ("toast.favorite_failed", Text("Favorite change failed")),
+    ("toast.comment_failed", Text("Comment has not been posted")),
+    ("toast.rejected", Text("{change} has been rejected")),
+    // -- article --
+    ("article.title", Text("Article")),
+    ("article.contents", Text("Contents")),
+    ("article.or", Text(" or ")),
+    ("article.to_comment", Text(" to comment.")),
+    ("article.comment_placeholder", Text("Write a comment...")),
+    ("article.post_comment", Text("Post Comment")),
+    ("article.favorite", Text("Favorite Article ({count})")),
+    ("article.copy_link", Text("Copy link to comment")),
+    ("article.link_copied", Text("Link copied")),
+    ("article.edit", Text("Edit Article")),
+    ("article.delete", Text("Delete Article")),
+    // -- editor --
+    ("editor.title_edit", Text("Edit Article - {slug}")),
+    ("editor.title_new", Text("New Article")),
+    ("editor.sign_in", Text("Sign in to edit this article.")),
+    ("editor.draft_restored", Text("Your unsaved draft has been restored. ")),
+    ("editor.all_drafts", Text("All drafts")),
+    ("editor.publish", Text("Publish Article")),
+    ("editor.update", Text("Update Article")),
+    ("editor.title_placeholder", Text("Article Title")),
+    ("editor.description_placeholder", Text("What's this article about?")),
+    ("editor.body_placeholder", Text("Write your article (in markdown)")),
+    ("editor.tags_placeholder", Text("Enter tags")),
+    ("editor.nothing_to_preview", Text("Nothing to preview.")),
+    ("editor.write", Text("Write")),
+    ("editor.preview", Text("Preview")),
+    // -- drafts --
+    ("drafts.empty", Text("No unsaved drafts.")),
+    ("drafts.untitled", Text("Untitled")),
+    ("drafts.published_changes", Text("Changes in a published article")),
+    ("drafts.new_article", Text("New article")),
+    ("drafts.discard", Text("Discard")),
+    // -- login --
+    ("login.title", Text("Login")),
+    ("login.need_account", Text("Need an account?")),
+    // -- register --
+    ("register.title", Text("Register")),
+    ("register.have_account", Text("Have an account?")),
+    // -- settings --
+    ("settings.sign_in", Text("Sign in to view your settings.")),
+    ("settings.update", Text("Update Settings")),
+    // -- form --
+    ("form.avatar", Text("URL of profile picture")),
+    ("form.username", Text("Your Name")),
+    ("form.bio", Text("Short bio about you")),
+    ("form.email", Text("Email")),
+    ("form.password", Text("Password")),
+    // -- not_found --
+    ("not_found.title", Text("Page Not Found")),
+    ("not_found.heading", Text("Not Found")),
+    // -- feed --
+    ("feed.read_more", Text("Read more...")),
+    ("feed.empty", Text("No articles are here... yet.")),
+    // -- author --
+    ("author.follow", Text("Follow {username}")),
+    ("author.unfollow", Text("Unfollow {username}")),
+    // -- outbox --
+    ("outbox.favorite", Text("Favorite \"{slug}\"")),
+    ("outbox.unfavorite", Text("Unfavorite \"{slug}\"")),
+    ("outbox.follow", Text("Follow {username}")),
+    ("outbox.unfollow", Text("Unfollow {username}")),
+    ("outbox.comment", Text("Comment on \"{slug}\"")),
+    ("outbox.new_article", Text("New article")),
+    ("outbox.article_changes", Text("Changes in \"{slug}\"")),
+    ("outbox.sending", Text("Sending pending changes...")),
+    ("outbox.waiting", Text("Pending changes - waiting for connection")),
+    // -- error --
+    ("error.network", Text("Cannot connect to the server")),
+    ("error.timeout", Text("Server did not respond in time")),
+    ("error.unauthorized", Text("You have to sign in")),
+    ("error.forbidden", Text("You are not allowed to do that")),
+    ("error.not_found", Text("Not found")),
+    ("error.status", Text("Server error ({code})")),
+    ("error.decode", Text("Data error at `{path}`")),
+    // -- problem --
+    ("problem.username_blank", Text("username can't be blank")),
+    ("problem.email_blank", Text("email can't be blank")),
+    ("problem.password_blank", Text("password can't be blank")),
+    ("problem.title_blank", Text("title can't be blank")),
+    ("problem.body_blank", Text("body can't be blank")),
+    (
+        "problem.password_too_short",
+        Text("password is too short (minimum is {min} characters)"),
+    ),
+    // -- markdown --
+    ("markdown.reading_time", Text("{count} min read")),
+    // -- time --
+    ("time.just_now", Text("just now")),
+    (
+        "time.minutes_ago",
+        Plural {
+            one: "{count} minute ago",
+            other: "{count} minutes ago",
+        },
+    ),
+    (
+        "time.hours_ago",
+        Plural {
+            one: "{count} hour ago",
+            other: "{count} hours ago",
+        },
+    ),
+    ("time.yesterday", Text("yesterday")),
+    (
+        "time.days_ago",
+        Plural {
+            one: "{count} day ago",
+            other: "{count} days ago",
+        },
+    ),
+    ("time.edited_at", Text("Edited {date}")),
+    ("time.edited", Text("(edited)")),
+];
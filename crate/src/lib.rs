@@ -7,34 +7,57 @@
 
 #[macro_use]
 extern crate seed;
-use entity::{article, username};
+use entity::{article, timestamp, username, ApiError, Viewer};
 use helper::take;
+use outbox::Outbox;
 use seed::prelude::*;
-use std::convert::TryInto;
+use std::{
+    collections::VecDeque,
+    convert::{TryFrom, TryInto},
+};
 
+use route::Access;
 pub use route::Route;
 pub use session::Session;
+use store::Store;
 
 mod coder;
+mod config;
 mod entity;
 mod helper;
+mod i18n;
 mod loading;
 mod logger;
+mod outbox;
 mod page;
 mod request;
 mod route;
+mod scroll_restoration;
 mod session;
 mod storage;
+mod store;
+mod toast;
 
 // ------ ------
 //     Model
 // ------ ------
 
-enum Model<'a> {
+#[derive(Default)]
+struct Model<'a> {
+    store: Store,
+    outbox: Outbox,
+    /// Saved accounts for switching in the header.
+    accounts: Vec<Viewer>,
+    toasts: VecDeque<toast::Toast>,
+    page: PageModel<'a>,
+}
+
+enum PageModel<'a> {
     Redirect(Session),
     NotFound(Session),
     Home(page::home::Model),
     Settings(page::settings::Model),
+    Drafts(page::drafts::Model),
     Login(page::login::Model),
     Register(page::register::Model),
     Profile(page::profile::Model<'a>, username::Username<'a>),
@@ -42,19 +65,20 @@ enum Model<'a> {
     ArticleEditor(page::article_editor::Model, Option<article::slug::Slug>),
 }
 
-impl<'a> Default for Model<'a> {
+impl<'a> Default for PageModel<'a> {
     fn default() -> Self {
-        Model::Redirect(Session::default())
+        PageModel::Redirect(Session::default())
     }
 }
 
-impl<'a> From<Model<'a>> for Session {
-    fn from(model: Model<'a>) -> Self {
-        use Model::*;
+impl<'a> From<PageModel<'a>> for Session {
+    fn from(model: PageModel<'a>) -> Self {
+        use PageModel::*;
         match model {
             Redirect(session) | NotFound(session) => session,
             Home(model) => model.into(),
             Settings(model) => model.into(),
+            Drafts(model) => model.into(),
             Login(model) => model.into(),
             Register(model) => model.into(),
             Profile(model, _) => model.into(),
@@ -64,6 +88,23 @@ impl<'a> From<Model<'a>> for Session {
     }
 }
 
+impl<'a> PageModel<'a> {
+    const fn session(&self) -> &Session {
+        use PageModel::*;
+        match self {
+            Redirect(session) | NotFound(session) => session,
+            Home(model) => model.session(),
+            Settings(model) => model.session(),
+            Drafts(model) => model.session(),
+            Login(model) => model.session(),
+            Register(model) => model.session(),
+            Profile(model, _) => model.session(),
+            Article(model) => model.session(),
+            ArticleEditor(model, _) => model.session(),
+        }
+    }
+}
+
 // ------ ------
 // Before Mount
 // ------ ------
@@ -80,14 +121,54 @@ fn before_mount(_: Url) -> BeforeMount {
 
 fn after_mount(
     url: Url,
-    orders: &mut impl Orders<Msg<'static>, GMsg>,
+    orders: &mut impl Orders<Msg<'static>, GMsg, AppMs = Msg<'static>>,
 ) -> AfterMount<Model<'static>> {
-    orders.send_msg(Msg::RouteChanged(url.try_into().ok()));
+    config::init();
+    i18n::init();
+    scroll_restoration::init();
+    let app = orders.clone_app();
+    request::set_unauthorized_handler(move || {
+        app.update(Msg::SessionExpired);
+    });
+    let app = orders.clone_app();
+    outbox::on_online(move || app.update(Msg::WentOnline));
+    let app = orders.clone_app();
+    outbox::on_changed(move || app.update(Msg::StoredOutboxChanged));
+    let app = orders.clone_app();
+    storage::on_viewer_changed(move || app.update(Msg::StoredViewerChanged));
+    // `WentOnline` sends mutations left from the previous visit
+    orders
+        .send_msg(Msg::RouteChanged(url.try_into().ok()))
+        .send_msg(Msg::WentOnline)
+        .perform_cmd(timestamp::notify_on_tick(Msg::ClockTicked));
 
-    let model = Model::Redirect(Session::new(storage::load_viewer()));
+    let model = Model {
+        page: PageModel::Redirect(Session::new(load_viewer())),
+        outbox: Outbox::load(),
+        accounts: load_accounts(),
+        ..Model::default()
+    };
     AfterMount::new(model).url_handling(UrlHandling::None)
 }
 
+/// Unreadable data shouldn't break the app - the user is a guest then.
+fn load_viewer() -> Option<Viewer> {
+    storage::load_viewer().unwrap_or_else(|error| {
+        logger::error(error);
+        None
+    })
+}
+
+fn load_accounts() -> Vec<Viewer> {
+    storage::load_accounts().map_or_else(
+        |error| {
+            logger::error(error);
+            Vec::new()
+        },
+        storage::Accounts::into_viewers,
+    )
+}
+
 // ------ ------
 //     Sink
 // ------ ------
@@ -95,6 +176,13 @@ fn after_mount(
 pub enum GMsg {
     RoutePushed(Route<'static>),
     SessionChanged(Session),
+    ToastRequested(toast::Toast),
+    /// The mutation will be sent once the browser is online.
+    MutationQueued(outbox::Mutation),
+    /// A comment from the outbox has been posted to the article.
+    CommentCreated(article::slug::Slug, entity::CommentId),
+    /// The page has loaded its data, so its scroll position can be restored.
+    PageLoaded,
 }
 
 fn sink<'a>(
@@ -102,52 +190,86 @@ fn sink<'a>(
     model: &mut Model<'a>,
     orders: &mut impl Orders<Msg<'static>, GMsg>,
 ) {
-    if let GMsg::RoutePushed(ref route) = g_msg {
-        orders.send_msg(Msg::RouteChanged(Some(route.clone())));
+    match g_msg {
+        GMsg::RoutePushed(ref route) => {
+            orders.send_msg(Msg::RouteChanged(Some(route.clone())));
+        },
+        GMsg::SessionChanged(ref session) => {
+            // stored entities depend on the viewer (e.g. `favorited`)
+            model.store = Store::default();
+            // queued mutations can't be sent by another account
+            let username = |session: &Session| {
+                session.viewer().map(|viewer| viewer.username().to_static())
+            };
+            if session.viewer().is_none()
+                || username(session) != username(model.page.session())
+            {
+                model.outbox.clear();
+            }
+            model.accounts = load_accounts();
+        },
+        GMsg::MutationQueued(mutation) => {
+            model.outbox.push(mutation);
+            return;
+        },
+        GMsg::CommentCreated(..) => (),
+        GMsg::PageLoaded => {
+            orders.after_next_render(|_| Msg::PageRendered);
+            return;
+        },
+        GMsg::ToastRequested(toast) => {
+            model.toasts.push_back(toast);
+            orders.perform_cmd(toast::notify_on_expiration(Msg::ToastExpired));
+            return;
+        },
     }
 
+    let model = &mut model.page;
     match model {
-        Model::NotFound(_) | Model::Redirect(_) => {
+        PageModel::NotFound(_) | PageModel::Redirect(_) => {
             if let GMsg::SessionChanged(session) = g_msg {
-                *model = Model::Redirect(session);
-                route::go_to(Route::Home, orders);
+                *model = PageModel::Redirect(session);
+                route::go_to(Route::Home(Default::default()), orders);
             }
         },
-        Model::Settings(model) => {
+        PageModel::Settings(model) => {
             page::settings::sink(
                 g_msg,
                 model,
                 &mut orders.proxy(Msg::SettingsMsg),
             );
         },
-        Model::Home(model) => {
-            page::home::sink(g_msg, model);
+        PageModel::Drafts(model) => {
+            page::drafts::sink(g_msg, model, &mut orders.proxy(Msg::DraftsMsg));
+        },
+        PageModel::Home(model) => {
+            page::home::sink(g_msg, model, &mut orders.proxy(Msg::HomeMsg));
         },
-        Model::Login(model) => {
+        PageModel::Login(model) => {
             page::login::sink(g_msg, model, &mut orders.proxy(Msg::LoginMsg));
         },
-        Model::Register(model) => {
+        PageModel::Register(model) => {
             page::register::sink(
                 g_msg,
                 model,
                 &mut orders.proxy(Msg::RegisterMsg),
             );
         },
-        Model::Profile(model, _) => {
+        PageModel::Profile(model, _) => {
             page::profile::sink(
                 g_msg,
                 model,
                 &mut orders.proxy(Msg::ProfileMsg),
             );
         },
-        Model::Article(model) => {
+        PageModel::Article(model) => {
             page::article::sink(
                 g_msg,
                 model,
                 &mut orders.proxy(Msg::ArticleMsg),
             );
         },
-        Model::ArticleEditor(model, _) => {
+        PageModel::ArticleEditor(model, _) => {
             page::article_editor::sink(
                 g_msg,
                 model,
@@ -164,8 +286,18 @@ fn sink<'a>(
 #[allow(clippy::enum_variant_names)]
 enum Msg<'a> {
     RouteChanged(Option<Route<'a>>),
+    SessionExpired,
+    ToastExpired,
+    StoredViewerChanged,
+    StoredOutboxChanged,
+    WentOnline,
+    ClockTicked,
+    LanguageSelected(i18n::Language),
+    PageRendered,
+    MutationReplayed(Result<outbox::Replayed, ApiError>),
     HomeMsg(page::home::Msg),
     SettingsMsg(page::settings::Msg),
+    DraftsMsg(page::drafts::Msg),
     LoginMsg(page::login::Msg),
     RegisterMsg(page::register::Msg),
     ProfileMsg(page::profile::Msg),
@@ -173,6 +305,7 @@ enum Msg<'a> {
     ArticleEditorMsg(page::article_editor::Msg),
 }
 
+#[allow(clippy::too_many_lines)]
 fn update<'a>(
     msg: Msg<'a>,
     model: &mut Model<'a>,
@@ -182,17 +315,90 @@ fn update<'a>(
         Msg::RouteChanged(route) => {
             change_model_by_route(route, model, orders);
         },
+        Msg::SessionExpired => {
+            if let Session::LoggedIn(_) = model.page.session() {
+                // the debounced draft save won't run once the page is replaced
+                if let PageModel::ArticleEditor(module_model, _) = &model.page {
+                    page::article_editor::save_draft(module_model);
+                }
+                remove_active_viewer();
+                let redirect = Route::try_from(seed::browser::url::current())
+                    .ok()
+                    .map(Box::new);
+                let route = Route::Login(redirect);
+                seed::push_route(route.clone());
+                model.page = PageModel::Redirect(Session::Guest);
+                change_model_by_route(Some(route), model, orders);
+                orders.send_g_msg(GMsg::SessionChanged(Session::Guest));
+            }
+        },
+        // the user has logged in or out in another tab
+        Msg::StoredViewerChanged => {
+            model.accounts = load_accounts();
+            let viewer = load_viewer();
+            let auth_token = |viewer: Option<&Viewer>| {
+                viewer.map(|viewer| viewer.auth_token.clone())
+            };
+            if auth_token(viewer.as_ref())
+                != auth_token(model.page.session().viewer())
+            {
+                orders.send_g_msg(GMsg::SessionChanged(Session::new(viewer)));
+            }
+        },
+        // another tab has queued or sent mutations
+        Msg::StoredOutboxChanged => model.outbox.reload(),
+        // re-render relative timestamps
+        Msg::ClockTicked => {
+            orders.perform_cmd(timestamp::notify_on_tick(Msg::ClockTicked));
+        },
+        Msg::LanguageSelected(language) => {
+            i18n::set_current(language);
+            if let Err(error) = storage::store_language(language) {
+                logger::error(error);
+            }
+        },
+        Msg::PageRendered => {
+            scroll_restoration::restore();
+            orders.skip();
+        },
+        Msg::ToastExpired => {
+            model.toasts.pop_front();
+        },
+        Msg::WentOnline => {
+            if !model.outbox.is_replaying() {
+                model.outbox.replay_next(
+                    model.page.session().viewer().cloned(),
+                    orders,
+                    Msg::MutationReplayed,
+                );
+            }
+        },
+        // keep the mutation and try again when the browser is online
+        Msg::MutationReplayed(Err(ApiError::Network | ApiError::Timeout)) => {
+            model.outbox.stop_replay();
+        },
+        Msg::MutationReplayed(result) => {
+            if let Some(mutation) = model.outbox.complete_replay() {
+                apply_replayed_mutation(&mutation, result, model, orders);
+            }
+            model.outbox.replay_next(
+                model.page.session().viewer().cloned(),
+                orders,
+                Msg::MutationReplayed,
+            );
+        },
         Msg::HomeMsg(module_msg) => {
-            if let Model::Home(module_model) = model {
+            if let PageModel::Home(module_model) = &mut model.page {
                 page::home::update(
                     module_msg,
                     module_model,
+                    &mut model.store,
                     &mut orders.proxy(Msg::HomeMsg),
                 );
             }
         },
         Msg::SettingsMsg(module_msg) => {
-            if let Model::Settings(module_model) = model {
+            if let PageModel::Settings(module_model) = &mut model.page {
                 page::settings::update(
                     module_msg,
                     module_model,
@@ -200,8 +406,13 @@ fn update<'a>(
                 );
             }
         },
+        Msg::DraftsMsg(module_msg) => {
+            if let PageModel::Drafts(module_model) = &mut model.page {
+                page::drafts::update(module_msg, module_model);
+            }
+        },
         Msg::LoginMsg(module_msg) => {
-            if let Model::Login(module_model) = model {
+            if let PageModel::Login(module_model) = &mut model.page {
                 page::login::update(
                     module_msg,
                     module_model,
@@ -210,7 +421,7 @@ fn update<'a>(
             }
         },
         Msg::RegisterMsg(module_msg) => {
-            if let Model::Register(module_model) = model {
+            if let PageModel::Register(module_model) = &mut model.page {
                 page::register::update(
                     module_msg,
                     module_model,
@@ -219,25 +430,27 @@ fn update<'a>(
             }
         },
         Msg::ProfileMsg(module_msg) => {
-            if let Model::Profile(module_model, _) = model {
+            if let PageModel::Profile(module_model, _) = &mut model.page {
                 page::profile::update(
                     module_msg,
                     module_model,
+                    &mut model.store,
                     &mut orders.proxy(Msg::ProfileMsg),
                 );
             }
         },
         Msg::ArticleMsg(module_msg) => {
-            if let Model::Article(module_model) = model {
+            if let PageModel::Article(module_model) = &mut model.page {
                 page::article::update(
                     module_msg,
                     module_model,
+                    &mut model.store,
                     &mut orders.proxy(Msg::ArticleMsg),
                 );
             }
         },
         Msg::ArticleEditorMsg(module_msg) => {
-            if let Model::ArticleEditor(module_model, _) = model {
+            if let PageModel::ArticleEditor(module_model, _) = &mut model.page {
                 page::article_editor::update(
                     module_msg,
                     module_model,
@@ -248,29 +461,95 @@ fn update<'a>(
     }
 }
 
+fn apply_replayed_mutation(
+    mutation: &outbox::Mutation,
+    result: Result<outbox::Replayed, ApiError>,
+    model: &mut Model,
+    orders: &mut impl Orders<Msg<'static>, GMsg>,
+) {
+    use outbox::Replayed;
+    match result {
+        Ok(Replayed::Article(article)) => {
+            model.store.insert_article(article);
+        },
+        Ok(Replayed::Author(author)) => {
+            model.store.insert_author(author);
+        },
+        Ok(Replayed::Comment(slug, comment)) => {
+            let comment_id = model.store.insert_comment(comment);
+            orders.send_g_msg(GMsg::CommentCreated(slug, comment_id));
+        },
+        // e.g. the article has been deleted in the meantime
+        Err(error) => {
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(toast::Toast::new_error(
+                i18n::t_with(
+                    "toast.rejected",
+                    &[("change", &mutation.description())],
+                ),
+                error,
+            )));
+        },
+    }
+}
+
+fn remove_active_viewer() {
+    if let Err(error) = storage::remove_active_viewer() {
+        logger::error(error);
+    }
+}
+
 fn change_model_by_route<'a>(
     route: Option<Route<'a>>,
     model: &mut Model<'a>,
     orders: &mut impl Orders<Msg<'static>, GMsg>,
 ) {
-    let mut session = || Session::from(take(model));
+    if let Some(redirect) = route.as_ref().and_then(|route| {
+        guard_redirect(route, model.page.session(), &model.store)
+    }) {
+        route::redirect_to(redirect, orders);
+        return;
+    }
+    scroll_restoration::on_route_changed();
+    let mut session = || Session::from(take(&mut model.page));
     match route {
-        None => *model = Model::NotFound(session()),
+        None => model.page = PageModel::NotFound(session()),
         Some(route) => match route {
-            Route::Root => route::go_to(Route::Home, orders),
+            Route::Root => {
+                route::go_to(Route::Home(Default::default()), orders)
+            },
             Route::Logout => {
-                storage::delete_app_data();
+                if let Some(viewer) = model.page.session().viewer() {
+                    if let Err(error) =
+                        storage::remove_drafts(viewer.username())
+                    {
+                        logger::error(error);
+                    }
+                }
+                remove_active_viewer();
                 orders.send_g_msg(GMsg::SessionChanged(Session::Guest));
-                route::go_to(Route::Home, orders)
+                route::go_to(Route::Home(Default::default()), orders)
+            },
+            Route::SwitchAccount(username) => {
+                match storage::activate_viewer(&username) {
+                    Ok(Some(viewer)) => {
+                        orders.send_g_msg(GMsg::SessionChanged(
+                            Session::LoggedIn(viewer),
+                        ));
+                    },
+                    Ok(None) => logger::error("Account to switch not found"),
+                    Err(error) => logger::error(error),
+                }
+                route::go_to(Route::Home(Default::default()), orders)
             },
             Route::NewArticle => {
-                *model = Model::ArticleEditor(
+                model.page = PageModel::ArticleEditor(
                     page::article_editor::init_new(session()),
                     None,
                 );
             },
             Route::EditArticle(slug) => {
-                *model = Model::ArticleEditor(
+                model.page = PageModel::ArticleEditor(
                     page::article_editor::init_edit(
                         session(),
                         slug.clone(),
@@ -280,42 +559,86 @@ fn change_model_by_route<'a>(
                 );
             },
             Route::Settings => {
-                *model = Model::Settings(page::settings::init(
+                model.page = PageModel::Settings(page::settings::init(
                     session(),
                     &mut orders.proxy(Msg::SettingsMsg),
                 ));
             },
-            Route::Home => {
-                *model = Model::Home(page::home::init(
+            Route::Drafts => {
+                model.page = PageModel::Drafts(page::drafts::init(session()));
+            },
+            Route::Home(query) => {
+                model.page = PageModel::Home(page::home::init(
                     session(),
+                    query,
                     &mut orders.proxy(Msg::HomeMsg),
                 ));
             },
-            Route::Login => {
-                *model = Model::Login(page::login::init(session()));
+            Route::Login(redirect) => {
+                model.page = PageModel::Login(page::login::init(
+                    session(),
+                    redirect.map(|redirect| *redirect),
+                ));
             },
             Route::Register => {
-                *model = Model::Register(page::register::init(session()));
+                model.page =
+                    PageModel::Register(page::register::init(session()));
             },
-            Route::Profile(username) => {
-                *model = Model::Profile(
+            Route::Profile(username, query) => {
+                model.page = PageModel::Profile(
                     page::profile::init(
                         session(),
                         username.to_static(),
+                        query,
                         &mut orders.proxy(Msg::ProfileMsg),
                     ),
                     username.into_owned(),
                 );
             },
-            Route::Article(slug) => {
-                *model = Model::Article(page::article::init(
+            Route::Article(slug, comment_id) => {
+                model.page = PageModel::Article(page::article::init(
                     session(),
                     &slug,
+                    comment_id,
                     &mut orders.proxy(Msg::ArticleMsg),
                 ));
             },
         },
     };
+    match model.page {
+        // they send `GMsg::PageLoaded`
+        PageModel::Home(_) | PageModel::Profile(..) | PageModel::Article(_) => {
+        },
+        _ => {
+            orders.after_next_render(|_| Msg::PageRendered);
+        },
+    }
+}
+
+/// The route to redirect to if the `session` can't visit the `route`.
+fn guard_redirect(
+    route: &Route,
+    session: &Session,
+    store: &Store,
+) -> Option<Route<'static>> {
+    let login = || Some(Route::Login(Some(Box::new(route.to_static()))));
+    match (route.access(), session.viewer()) {
+        (Access::Public, _)
+        | (Access::GuestOnly, None)
+        | (Access::AuthRequired, Some(_)) => None,
+        (Access::GuestOnly, Some(_)) => Some(Route::Home(Default::default())),
+        (Access::AuthRequired, None) | (Access::OwnerRequired, None) => login(),
+        (Access::OwnerRequired, Some(viewer)) => match route {
+            // articles that aren't in the store are checked by the editor
+            Route::EditArticle(slug) => store
+                .article(slug)
+                .filter(|article| {
+                    article.author.username() != viewer.username()
+                })
+                .map(|_| Route::Article(slug.clone(), None)),
+            _ => None,
+        },
+    }
 }
 
 // ------ ------
@@ -324,38 +647,78 @@ fn change_model_by_route<'a>(
 
 fn view(model: &Model) -> impl View<Msg<'static>> {
     use page::Page;
-    match model {
-        Model::Redirect(session) => {
-            Page::Other.view(page::blank::view(), session.viewer())
-        },
-        Model::NotFound(session) => {
-            Page::Other.view(page::not_found::view(), session.viewer())
+    let store = &model.store;
+    let outbox = &model.outbox;
+    let accounts = &model.accounts;
+    let mut nodes = match &model.page {
+        PageModel::Redirect(session) => {
+            Page::Other.view(page::blank::view(), session.viewer(), accounts)
         },
-        Model::Settings(model) => Page::Settings
-            .view(page::settings::view(model), model.session().viewer())
+        PageModel::NotFound(session) => Page::Other.view(
+            page::not_found::view(),
+            session.viewer(),
+            accounts,
+        ),
+        PageModel::Settings(model) => Page::Settings
+            .view(
+                page::settings::view(model),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::SettingsMsg),
-        Model::Home(model) => Page::Home
-            .view(page::home::view(model), model.session().viewer())
+        PageModel::Drafts(model) => Page::Drafts
+            .view(page::drafts::view(model), model.session().viewer(), accounts)
+            .map_msg(Msg::DraftsMsg),
+        PageModel::Home(model) => Page::Home
+            .view(
+                page::home::view(model, store),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::HomeMsg),
-        Model::Login(model) => Page::Login
-            .view(page::login::view(model), model.session().viewer())
+        PageModel::Login(model) => Page::Login
+            .view(page::login::view(model), model.session().viewer(), accounts)
             .map_msg(Msg::LoginMsg),
-        Model::Register(model) => Page::Register
-            .view(page::register::view(model), model.session().viewer())
+        PageModel::Register(model) => Page::Register
+            .view(
+                page::register::view(model),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::RegisterMsg),
-        Model::Profile(model, username) => Page::Profile(username)
-            .view(page::profile::view(model), model.session().viewer())
+        PageModel::Profile(model, username) => Page::Profile(username)
+            .view(
+                page::profile::view(model, store),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::ProfileMsg),
-        Model::Article(model) => Page::Other
-            .view(page::article::view(model), model.session().viewer())
+        PageModel::Article(model) => Page::Other
+            .view(
+                page::article::view(model, store, outbox),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::ArticleMsg),
-        Model::ArticleEditor(model, None) => Page::NewArticle
-            .view(page::article_editor::view(model), model.session().viewer())
+        PageModel::ArticleEditor(model, None) => Page::NewArticle
+            .view(
+                page::article_editor::view(model),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::ArticleEditorMsg),
-        Model::ArticleEditor(model, Some(_)) => Page::Other
-            .view(page::article_editor::view(model), model.session().viewer())
+        PageModel::ArticleEditor(model, Some(_)) => Page::Other
+            .view(
+                page::article_editor::view(model),
+                model.session().viewer(),
+                accounts,
+            )
             .map_msg(Msg::ArticleEditorMsg),
-    }
+    };
+    nodes.push(page::view_footer(Msg::LanguageSelected));
+    nodes.push(outbox::view(outbox));
+    nodes.push(toast::view(&model.toasts));
+    nodes
 }
 
 // ------ ------
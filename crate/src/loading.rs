@@ -20,6 +20,6 @@ pub fn view_icon<Ms>() -> Node<Ms> {
     }]
 }
 
-pub fn view_error<Ms>(subject: &str) -> Node<Ms> {
-    div![format!("Error loading {}.", subject)]
+pub fn view_error<Ms>(message: &str) -> Node<Ms> {
+    div![message]
 }
@@ -0,0 +1,392 @@
+use crate::{
+    entity::{
+        form::article_editor::ValidForm, ApiError, Article, Author, Comment,
+        Slug, Username, Viewer,
+    },
+    i18n, logger, request, storage, GMsg,
+};
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use wasm_bindgen::JsCast;
+
+// ------ Mutation ------
+
+/// Change that couldn't be sent because the browser was offline.
+#[derive(Serialize, Deserialize)]
+pub enum Mutation {
+    Favorite(Slug),
+    Unfavorite(Slug),
+    Follow(Username<'static>),
+    Unfollow(Username<'static>),
+    CreateComment(Slug, String),
+    CreateArticle(ValidForm),
+    UpdateArticle(Slug, ValidForm),
+}
+
+impl Mutation {
+    /// The change optimistically applied to the `original` article.
+    pub fn new_favorite_change(original: &Article) -> Self {
+        let slug = original.slug.clone();
+        if original.favorited {
+            Self::Unfavorite(slug)
+        } else {
+            Self::Favorite(slug)
+        }
+    }
+
+    /// The change optimistically applied to the `original` author.
+    pub fn new_follow_change(original: &Author) -> Self {
+        let username = original.username().to_static();
+        match original {
+            Author::Following(_) => Self::Unfollow(username),
+            _ => Self::Follow(username),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::Favorite(slug) => {
+                i18n::t_with("outbox.favorite", &[("slug", &slug.as_str())])
+            },
+            Self::Unfavorite(slug) => {
+                i18n::t_with("outbox.unfavorite", &[("slug", &slug.as_str())])
+            },
+            Self::Follow(username) => i18n::t_with(
+                "outbox.follow",
+                &[("username", &username.as_str())],
+            ),
+            Self::Unfollow(username) => i18n::t_with(
+                "outbox.unfollow",
+                &[("username", &username.as_str())],
+            ),
+            Self::CreateComment(slug, _) => {
+                i18n::t_with("outbox.comment", &[("slug", &slug.as_str())])
+            },
+            Self::CreateArticle(_) => i18n::t("outbox.new_article").to_owned(),
+            Self::UpdateArticle(slug, _) => i18n::t_with(
+                "outbox.article_changes",
+                &[("slug", &slug.as_str())],
+            ),
+        }
+    }
+}
+
+// ------ ReplayLock ------
+
+/// Only one tab replays the outbox at a time,
+/// otherwise the mutations would be sent more than once.
+#[derive(Serialize, Deserialize)]
+pub struct ReplayLock {
+    owner: String,
+    /// The lock of a closed tab must not block the other tabs forever.
+    expires_at: f64,
+}
+
+/// Longer than a request timeout, it's renewed before each replayed mutation.
+const REPLAY_LOCK_DURATION_MS: f64 = 30_000.;
+
+// ------ Replayed ------
+
+/// Entity returned by the API for a replayed mutation.
+pub enum Replayed {
+    Article(Article),
+    Author(Author),
+    Comment(Slug, Comment),
+}
+
+// ------ Outbox ------
+
+/// Queue of mutations that are sent in order once the browser is online.
+///
+/// It's persisted in the local storage, so it survives page reloads.
+/// The storage is shared by all tabs, so it's re-read before each change.
+#[derive(Default)]
+pub struct Outbox {
+    mutations: VecDeque<Mutation>,
+    replaying: bool,
+    /// Identifies this tab in the `ReplayLock`.
+    id: String,
+}
+
+impl Outbox {
+    pub fn load() -> Self {
+        Self {
+            mutations: load_mutations(),
+            replaying: false,
+            id: format!("{}-{}", js_sys::Date::now(), js_sys::Math::random()),
+        }
+    }
+
+    /// Pick up the changes made by other tabs.
+    pub fn reload(&mut self) {
+        self.mutations = load_mutations();
+    }
+
+    pub fn push(&mut self, mutation: Mutation) {
+        self.reload();
+        self.mutations.push_back(mutation);
+        self.store();
+    }
+
+    pub fn clear(&mut self) {
+        self.mutations.clear();
+        self.stop_replay();
+        self.store();
+    }
+
+    fn store(&self) {
+        if let Err(error) = storage::store_outbox(&self.mutations) {
+            logger::error(error);
+        }
+    }
+
+    /// Texts of comments waiting to be posted to the article.
+    pub fn comments<'a>(
+        &'a self,
+        slug: &'a Slug,
+    ) -> impl DoubleEndedIterator<Item = &'a str> {
+        self.mutations.iter().filter_map(move |mutation| match mutation {
+            Mutation::CreateComment(comment_slug, text)
+                if comment_slug == slug =>
+            {
+                Some(text.as_str())
+            },
+            _ => None,
+        })
+    }
+
+    pub const fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
+    /// Send the first mutation; `f` receives the result.
+    /// Replaying stops when the outbox is empty
+    /// or when another tab is replaying it.
+    pub fn replay_next<Ms: 'static>(
+        &mut self,
+        viewer: Option<Viewer>,
+        orders: &mut impl Orders<Ms, GMsg>,
+        f: fn(Result<Replayed, ApiError>) -> Ms,
+    ) {
+        self.reload();
+        if self.mutations.is_empty() || !self.lock() {
+            return self.stop_replay();
+        }
+        self.replaying = true;
+        if let Some(mutation) = self.mutations.front() {
+            replay(mutation, viewer, orders, f);
+        }
+    }
+
+    /// Stop replaying and keep the mutation for the next attempt.
+    pub fn stop_replay(&mut self) {
+        if self.replaying {
+            self.unlock();
+        }
+        self.replaying = false;
+    }
+
+    /// Remove the mutation whose replay has finished.
+    /// Other tabs only append, so it's still the first one.
+    pub fn complete_replay(&mut self) -> Option<Mutation> {
+        self.reload();
+        let mutation = self.mutations.pop_front();
+        self.store();
+        mutation
+    }
+
+    /// Acquire or renew the `ReplayLock`.
+    /// Returns `false` if another tab holds it.
+    fn lock(&self) -> bool {
+        let now = js_sys::Date::now();
+        let locked_by_other =
+            |lock: &ReplayLock| lock.owner != self.id && lock.expires_at > now;
+        match storage::load_outbox_lock() {
+            Ok(Some(lock)) if locked_by_other(&lock) => return false,
+            Ok(_) => (),
+            Err(error) => logger::error(error),
+        }
+        let lock = ReplayLock {
+            owner: self.id.clone(),
+            expires_at: now + REPLAY_LOCK_DURATION_MS,
+        };
+        if let Err(error) = storage::store_outbox_lock(&lock) {
+            // without the storage there is nothing to coordinate with
+            logger::error(error);
+            return true;
+        }
+        // another tab may have written its lock at the same time
+        !matches!(
+            storage::load_outbox_lock(),
+            Ok(Some(lock)) if locked_by_other(&lock)
+        )
+    }
+
+    fn unlock(&self) {
+        match storage::load_outbox_lock() {
+            Ok(Some(lock)) if lock.owner == self.id => {
+                if let Err(error) = storage::remove_outbox_lock() {
+                    logger::error(error);
+                }
+            },
+            Ok(_) => (),
+            Err(error) => logger::error(error),
+        }
+    }
+}
+
+/// Call `handler` whenever the browser goes online.
+pub fn on_online(handler: impl Fn() + 'static) {
+    let closure = Closure::wrap(Box::new(handler) as Box<dyn Fn()>);
+    seed::window()
+        .add_event_listener_with_callback(
+            "online",
+            closure.as_ref().unchecked_ref(),
+        )
+        .expect("add online event listener failed");
+    // the listener lives as long as the app
+    closure.forget();
+}
+
+/// Call `handler` when another tab changes the outbox.
+pub fn on_changed(handler: impl Fn() + 'static) {
+    storage::on_outbox_changed(handler);
+}
+
+// ------ view functions ------
+
+pub fn view<Ms>(outbox: &Outbox) -> Node<Ms> {
+    if outbox.mutations.is_empty() {
+        return empty![];
+    }
+    div![
+        style! {
+            "position" => "fixed",
+            "left" => "20px",
+            "bottom" => "20px",
+            "z-index" => 9999,
+            "background" => "rgb(250, 250, 250)",
+            "padding" => "10px 20px",
+            "border" => "1px solid",
+            "opacity" => 0.8,
+        },
+        strong![if outbox.replaying {
+            i18n::t("outbox.sending")
+        } else {
+            i18n::t("outbox.waiting")
+        }],
+        ul![outbox
+            .mutations
+            .iter()
+            .map(|mutation| li![mutation.description()])]
+    ]
+}
+
+// ====== PRIVATE ======
+
+fn load_mutations() -> VecDeque<Mutation> {
+    storage::load_outbox().unwrap_or_else(|error| {
+        logger::error(error);
+        VecDeque::new()
+    })
+}
+
+fn replay<Ms: 'static>(
+    mutation: &Mutation,
+    viewer: Option<Viewer>,
+    orders: &mut impl Orders<Ms, GMsg>,
+    f: fn(Result<Replayed, ApiError>) -> Ms,
+) {
+    match mutation {
+        Mutation::Favorite(slug) => orders.perform_cmd(
+            request::favorite::favorite(viewer, slug, move |result| {
+                f(result.map(Replayed::Article))
+            }),
+        ),
+        Mutation::Unfavorite(slug) => orders.perform_cmd(
+            request::favorite::unfavorite(viewer, slug, move |result| {
+                f(result.map(Replayed::Article))
+            }),
+        ),
+        Mutation::Follow(username) => orders.perform_cmd(
+            request::follow::follow(viewer, username, move |result| {
+                f(result.map(Replayed::Author))
+            }),
+        ),
+        Mutation::Unfollow(username) => orders.perform_cmd(
+            request::follow::unfollow(viewer, username, move |result| {
+                f(result.map(Replayed::Author))
+            }),
+        ),
+        Mutation::CreateComment(slug, text) => {
+            let comment_slug = slug.clone();
+            orders.perform_cmd(request::comment::create(
+                viewer,
+                slug,
+                text.clone(),
+                move |result| {
+                    f(result.map(|comment| {
+                        Replayed::Comment(comment_slug, comment)
+                    }))
+                },
+            ))
+        },
+        Mutation::CreateArticle(form) => orders.perform_cmd(
+            request::article::create(viewer, form, move |result| {
+                f(result.map(Replayed::Article))
+            }),
+        ),
+        Mutation::UpdateArticle(slug, form) => orders.perform_cmd(
+            request::article::update(viewer, form, slug, move |result| {
+                f(result.map(Replayed::Article))
+            }),
+        ),
+    };
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn clean_local_storage() {
+        seed::storage::get_storage()
+            .expect("get local storage failed")
+            .clear()
+            .expect("clear storage failed");
+    }
+
+    #[wasm_bindgen_test]
+    fn shared_outbox_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        let mut first_tab = Outbox::load();
+        let mut second_tab = Outbox::load();
+
+        // ====== ACT ======
+        first_tab.push(Mutation::Favorite(Slug::from("a".to_owned())));
+        second_tab.push(Mutation::Follow("john".into()));
+        let first_tab_locked = first_tab.lock();
+        let second_tab_locked = second_tab.lock();
+        first_tab.replaying = true;
+        first_tab.complete_replay();
+        first_tab.stop_replay();
+        let second_tab_locked_later = second_tab.lock();
+        second_tab.reload();
+
+        // ====== ASSERT ======
+        assert!(first_tab_locked);
+        assert!(!second_tab_locked);
+        assert!(second_tab_locked_later);
+        assert_eq!(second_tab.mutations.len(), 1);
+        assert!(matches!(
+            second_tab.mutations.front(),
+            Some(Mutation::Follow(username)) if username.as_str() == "john"
+        ));
+    }
+}
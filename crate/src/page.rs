@@ -1,13 +1,17 @@
 use crate::{
     entity::{ErrorMessage, Username, Viewer},
+    i18n::{self, Language},
     Route,
 };
 use seed::prelude::*;
 use std::borrow::{Borrow, Cow};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 
 pub mod article;
 pub mod article_editor;
 pub mod blank;
+pub mod drafts;
 pub mod home;
 pub mod login;
 pub mod not_found;
@@ -24,6 +28,33 @@ pub fn scroll_to_top() {
     )
 }
 
+pub fn scroll_to_element(id: &str) {
+    if let Some(element) = seed::document().get_element_by_id(id) {
+        element.scroll_into_view_with_scroll_into_view_options(
+            web_sys::ScrollIntoViewOptions::new()
+                .behavior(web_sys::ScrollBehavior::Smooth),
+        )
+    }
+}
+
+/// The route with the current origin, e.g. to share it.
+pub fn absolute_url(route: &Route) -> String {
+    let origin = seed::window().location().origin().unwrap_or_default();
+    format!("{}{}", origin, route)
+}
+
+pub async fn copy_to_clipboard(text: &str) -> Result<(), JsValue> {
+    // `web_sys::Clipboard` is available only with unstable APIs enabled
+    let clipboard =
+        js_sys::Reflect::get(&seed::window().navigator(), &"clipboard".into())?;
+    let write_text = js_sys::Reflect::get(&clipboard, &"writeText".into())?
+        .dyn_into::<js_sys::Function>()?;
+    let promise = write_text
+        .call1(&clipboard, &text.into())?
+        .dyn_into::<js_sys::Promise>()?;
+    JsFuture::from(promise).await.map(drop)
+}
+
 pub fn view_errors<Ms: Clone>(
     dismiss_errors: Ms,
     errors: &[ErrorMessage],
@@ -42,11 +73,37 @@ pub fn view_errors<Ms: Clone>(
                 "z-index" => 9999,
             },
             errors.iter().map(|error| p![error]),
-            button![simple_ev(Ev::Click, dismiss_errors), "Ok"]
+            button![
+                simple_ev(Ev::Click, dismiss_errors),
+                i18n::t("errors.dismiss")
+            ]
         ]
     }
 }
 
+pub fn view_footer<Ms: 'static>(
+    on_language_selected: impl FnOnce(Language) -> Ms + Clone + 'static,
+) -> Node<Ms> {
+    footer![div![
+        class!["container"],
+        a![
+            class!["logo-font"],
+            attrs! {At::Href => Route::Home(Default::default()).to_string()},
+            "conduit"
+        ],
+        span![
+            class!["attribution"],
+            i18n::t("footer.attribution_prefix"),
+            a![attrs! {At::Href => "https://thinkster.io"}, "Thinkster"],
+            i18n::t("footer.attribution_suffix")
+        ],
+        span![
+            class!["attribution", "pull-xs-right"],
+            i18n::view_language_picker(on_language_selected)
+        ]
+    ]]
+}
+
 // ------ ViewPage ------
 
 #[allow(clippy::module_name_repetitions)]
@@ -86,18 +143,20 @@ pub enum Page<'a> {
     Settings,
     Profile(&'a Username<'a>),
     NewArticle,
+    Drafts,
 }
 
 #[allow(clippy::unused_self)]
 impl<'a> Page<'a> {
     fn is_active(&self, route: &Route) -> bool {
         match (self, route) {
-            (Page::Home, Route::Home)
-            | (Page::Login, Route::Login)
+            (Page::Home, Route::Home(_))
+            | (Page::Login, Route::Login(_))
             | (Page::Register, Route::Register)
             | (Page::Settings, Route::Settings)
-            | (Page::NewArticle, Route::NewArticle) => true,
-            (Page::Profile(username), Route::Profile(route_username)) => {
+            | (Page::NewArticle, Route::NewArticle)
+            | (Page::Drafts, Route::Drafts) => true,
+            (Page::Profile(username), Route::Profile(route_username, _)) => {
                 *username == route_username.borrow()
             },
             _ => false,
@@ -110,54 +169,42 @@ impl<'a> Page<'a> {
         &self,
         view_page: ViewPage<'a, Ms>,
         viewer: Option<&Viewer>,
+        accounts: &[Viewer],
     ) -> Vec<Node<Ms>> {
         seed::document().set_title(&view_page.title());
 
-        vec![
-            self.view_header(viewer),
-            view_page.into_content(),
-            self.view_footer(),
-        ]
+        vec![self.view_header(viewer, accounts), view_page.into_content()]
     }
 
     // ====== PRIVATE ======
 
-    fn view_header<Ms>(&self, viewer: Option<&Viewer>) -> Node<Ms> {
+    fn view_header<Ms>(
+        &self,
+        viewer: Option<&Viewer>,
+        accounts: &[Viewer],
+    ) -> Node<Ms> {
         nav![
             class!["navbar", "navbar-light"],
             div![
                 class!["container"],
                 a![
                     class!["navbar-brand"],
-                    attrs! {At::Href => Route::Home.to_string()},
+                    attrs! {At::Href => Route::Home(Default::default()).to_string()},
                     "conduit"
                 ],
                 ul![
                     class!["nav navbar-nav pull-xs-right"],
-                    self.view_navbar_link(&Route::Home, "Home"),
+                    self.view_navbar_link(
+                        &Route::Home(Default::default()),
+                        i18n::t("nav.home")
+                    ),
                     self.view_menu(viewer),
+                    self.view_account_links(viewer, accounts),
                 ],
             ]
         ]
     }
 
-    fn view_footer<Ms>(&self) -> Node<Ms> {
-        footer![div![
-            class!["container"],
-            a![
-                class!["logo-font"],
-                attrs! {At::Href => Route::Home.to_string()},
-                "conduit"
-            ],
-            span![
-                class!["attribution"],
-                "An interactive learning project from ",
-                a![attrs! {At::Href => "https://thinkster.io"}, "Thinkster"],
-                ". Code & design licensed under MIT."
-            ]
-        ]]
-    }
-
     // ------ view_header helpers ------
 
     fn view_navbar_link<Ms>(
@@ -181,20 +228,45 @@ impl<'a> Page<'a> {
     fn view_menu<Ms>(&self, viewer: Option<&Viewer>) -> Vec<Node<Ms>> {
         match viewer {
             None => vec![
-                self.view_navbar_link(&Route::Login, "Sign in"),
-                self.view_navbar_link(&Route::Register, "Sign up"),
+                self.view_navbar_link(
+                    &Route::Login(None),
+                    i18n::t("auth.sign_in"),
+                ),
+                self.view_navbar_link(
+                    &Route::Register,
+                    i18n::t("auth.sign_up"),
+                ),
             ],
             Some(viewer) => vec![
                 self.view_navbar_link(
                     &Route::NewArticle,
-                    vec![i![class!["ion-compose"]], plain!("\u{00A0}New Post")],
+                    vec![
+                        i![class!["ion-compose"]],
+                        plain!(format!(
+                            "\u{00A0}{}",
+                            i18n::t("nav.new_article")
+                        )),
+                    ],
+                ),
+                self.view_navbar_link(
+                    &Route::Drafts,
+                    vec![
+                        i![class!["ion-document"]],
+                        plain!(format!("\u{00A0}{}", i18n::t("nav.drafts"))),
+                    ],
                 ),
                 self.view_navbar_link(
                     &Route::Settings,
-                    vec![i![class!["ion-gear-a"]], plain!("\u{00A0}Settings")],
+                    vec![
+                        i![class!["ion-gear-a"]],
+                        plain!(format!("\u{00A0}{}", i18n::t("nav.settings"))),
+                    ],
                 ),
                 self.view_navbar_link(
-                    &Route::Profile(Cow::Borrowed(viewer.username())),
+                    &Route::Profile(
+                        Cow::Borrowed(viewer.username()),
+                        Default::default(),
+                    ),
                     vec![
                         img![
                             class!["user-pic"],
@@ -203,8 +275,46 @@ impl<'a> Page<'a> {
                         plain!(viewer.username().to_string()),
                     ],
                 ),
-                self.view_navbar_link(&Route::Logout, "Sign out"),
+                self.view_navbar_link(
+                    &Route::Login(None),
+                    vec![
+                        i![class!["ion-person-add"]],
+                        plain!(format!(
+                            "\u{00A0}{}",
+                            i18n::t("nav.add_account")
+                        )),
+                    ],
+                ),
+                self.view_navbar_link(&Route::Logout, i18n::t("auth.sign_out")),
             ],
         }
     }
+
+    /// Links to switch to other saved accounts.
+    fn view_account_links<Ms>(
+        &self,
+        viewer: Option<&Viewer>,
+        accounts: &[Viewer],
+    ) -> Vec<Node<Ms>> {
+        accounts
+            .iter()
+            .filter(|account| {
+                Some(account.username()) != viewer.map(Viewer::username)
+            })
+            .map(|account| {
+                self.view_navbar_link(
+                    &Route::SwitchAccount(Cow::Borrowed(account.username())),
+                    vec![
+                        i![class!["ion-arrow-swap"]],
+                        plain!("\u{00A0}"),
+                        img![
+                            class!["user-pic"],
+                            attrs! {At::Src => account.avatar().src()}
+                        ],
+                        plain!(account.username().to_string()),
+                    ],
+                )
+            })
+            .collect()
+    }
 }
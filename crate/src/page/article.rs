@@ -2,11 +2,17 @@ use super::ViewPage;
 use crate::{
     entity::{
         author::{self, Author},
-        timestamp, Article, Comment, CommentId, ErrorMessage, Slug,
+        markdown::{self, Heading},
+        timestamp, ApiError, Article, Comment, CommentId, ErrorMessage, Slug,
     },
     helper::take,
-    loading, logger, page, request,
+    i18n, loading, logger,
+    outbox::{Mutation, Outbox},
+    page,
+    request::{self, RequestId},
     route::{self, Route},
+    store::Store,
+    toast::Toast,
     GMsg, Session,
 };
 use seed::prelude::*;
@@ -22,8 +28,13 @@ use std::{borrow::Cow, collections::VecDeque};
 pub struct Model {
     session: Session,
     errors: Vec<ErrorMessage>,
-    comments: Status<(CommentText, VecDeque<Comment>)>,
-    article: Status<Article>,
+    request_id: RequestId,
+    /// The comment from the URL; it's scrolled to and highlighted.
+    target_comment: Option<CommentId>,
+    /// The comment whose link has been copied to the clipboard.
+    copied_comment: Option<CommentId>,
+    comments: Status<(String, VecDeque<CommentId>)>,
+    article: Status<Slug>,
 }
 
 impl Model {
@@ -40,29 +51,21 @@ impl From<Model> for Session {
 
 // ------ Status ------
 
+#[derive(Default)]
 enum Status<T> {
+    #[default]
     Loading,
     LoadingSlowly,
     Loaded(T),
     Failed,
 }
 
-impl<T> Default for Status<T> {
-    fn default() -> Self {
-        Self::Loading
-    }
-}
-
-// ------ CommentText ------
-
-enum CommentText {
-    Editing(String),
-    Sending(String),
-}
-
-impl Default for CommentText {
-    fn default() -> Self {
-        Self::Editing("".into())
+impl<T> Status<T> {
+    const fn is_loading(&self) -> bool {
+        match self {
+            Self::Loading | Self::LoadingSlowly => true,
+            Self::Loaded(_) | Self::Failed => false,
+        }
     }
 }
 
@@ -73,23 +76,29 @@ impl Default for CommentText {
 pub fn init(
     session: Session,
     slug: &Slug,
+    target_comment: Option<CommentId>,
     orders: &mut impl Orders<Msg, GMsg>,
 ) -> Model {
+    let request_id = RequestId::new();
+
     orders
         .perform_cmd(loading::notify_on_slow_load(Msg::SlowLoadThresholdPassed))
-        .perform_cmd(request::article::load(
-            session.viewer().cloned(),
-            slug,
-            Msg::LoadArticleCompleted,
-        ))
         .perform_cmd(request::comment::load_list(
             session.viewer().cloned(),
             slug,
-            Msg::LoadCommentsCompleted,
+            move |result| Msg::LoadCommentsCompleted(request_id, result),
         ));
+    request::article::load(
+        orders,
+        session.viewer().cloned(),
+        slug,
+        move |result| Msg::LoadArticleCompleted(request_id, result),
+    );
 
     Model {
         session,
+        request_id,
+        target_comment,
         ..Model::default()
     }
 }
@@ -106,7 +115,12 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            route::go_to(Route::Home(Default::default()), orders);
+        },
+        GMsg::CommentCreated(slug, comment_id) => {
+            if let Some((_, comment_ids)) = loaded_comments(model, &slug) {
+                comment_ids.push_front(comment_id);
+            }
         },
         _ => (),
     }
@@ -127,13 +141,21 @@ pub enum Msg {
     UnfollowClicked(Author),
     PostCommentClicked(Slug),
     CommentTextEntered(String),
-    LoadArticleCompleted(Result<Article, Vec<ErrorMessage>>),
-    LoadCommentsCompleted(Result<VecDeque<Comment>, Vec<ErrorMessage>>),
-    DeleteArticleCompleted(Result<(), Vec<ErrorMessage>>),
-    DeleteCommentCompleted(Result<CommentId, Vec<ErrorMessage>>),
-    FavoriteChangeCompleted(Result<Article, Vec<ErrorMessage>>),
-    FollowChangeCompleted(Result<Author, Vec<ErrorMessage>>),
-    PostCommentCompleted(Result<Comment, Vec<ErrorMessage>>),
+    /// Anchor of the heading.
+    HeadingClicked(String),
+    CopyCommentLinkClicked(Slug, CommentId),
+    CommentLinkCopied(CommentId, Result<(), JsValue>),
+    TargetCommentRendered,
+    LoadArticleCompleted(RequestId, Result<Article, ApiError>),
+    LoadCommentsCompleted(RequestId, Result<VecDeque<Comment>, ApiError>),
+    DeleteArticleCompleted(Result<(), ApiError>),
+    DeleteCommentCompleted(Result<CommentId, ApiError>),
+    /// The first article is the original - `favorited` is restored on error.
+    FavoriteChangeCompleted(Article, Result<Article, ApiError>),
+    /// The first author is the original - `following` is restored on error.
+    FollowChangeCompleted(Author, Result<Author, ApiError>),
+    /// The article, id of the pending comment and its text.
+    PostCommentCompleted(Slug, CommentId, String, Result<Comment, ApiError>),
     SlowLoadThresholdPassed,
 }
 
@@ -141,6 +163,7 @@ pub enum Msg {
 pub fn update(
     msg: Msg,
     model: &mut Model,
+    store: &mut Store,
     orders: &mut impl Orders<Msg, GMsg>,
 ) {
     match msg {
@@ -166,145 +189,262 @@ pub fn update(
         Msg::DismissErrorsClicked => {
             model.errors.clear();
         },
-        Msg::FavoriteClicked(slug) => {
+        Msg::HeadingClicked(anchor) => {
+            page::scroll_to_element(&anchor);
+            orders.skip();
+        },
+        Msg::CopyCommentLinkClicked(slug, comment_id) => {
+            let link = page::absolute_url(&Route::Article(
+                slug,
+                Some(comment_id.clone()),
+            ));
             orders
-                .perform_cmd(request::favorite::unfavorite(
+                .perform_cmd(async move {
+                    let result = page::copy_to_clipboard(&link).await;
+                    Ok::<_, Msg>(Msg::CommentLinkCopied(comment_id, result))
+                })
+                .skip();
+        },
+        Msg::CommentLinkCopied(comment_id, Ok(())) => {
+            model.copied_comment = Some(comment_id);
+        },
+        Msg::CommentLinkCopied(_, Err(error)) => {
+            logger::error(error);
+        },
+        Msg::TargetCommentRendered => {
+            if let Some(comment_id) = &model.target_comment {
+                page::scroll_to_element(&comment_id.anchor());
+            }
+            orders.skip();
+        },
+        Msg::FavoriteClicked(slug) => {
+            if let Some(original) = store
+                .change_article(&slug, |article| article.set_favorited(false))
+            {
+                orders.perform_cmd(request::favorite::unfavorite(
                     model.session.viewer().cloned(),
                     &slug,
-                    Msg::FavoriteChangeCompleted,
-                ))
-                .skip();
+                    move |result| {
+                        Msg::FavoriteChangeCompleted(original, result)
+                    },
+                ));
+            }
         },
         Msg::UnfavoriteClicked(slug) => {
-            orders
-                .perform_cmd(request::favorite::favorite(
+            if let Some(original) = store
+                .change_article(&slug, |article| article.set_favorited(true))
+            {
+                orders.perform_cmd(request::favorite::favorite(
                     model.session.viewer().cloned(),
                     &slug,
-                    Msg::FavoriteChangeCompleted,
-                ))
-                .skip();
+                    move |result| {
+                        Msg::FavoriteChangeCompleted(original, result)
+                    },
+                ));
+            }
         },
         Msg::FollowClicked(author) => {
-            orders
-                .perform_cmd(request::follow::follow(
+            if let Some(original) = store
+                .change_author(author.username(), |author| {
+                    author.set_following(true)
+                })
+            {
+                orders.perform_cmd(request::follow::follow(
                     model.session.viewer().cloned(),
                     author.username(),
-                    Msg::FollowChangeCompleted,
-                ))
-                .skip();
+                    move |result| Msg::FollowChangeCompleted(original, result),
+                ));
+            }
         },
         Msg::UnfollowClicked(author) => {
-            orders
-                .perform_cmd(request::follow::unfollow(
+            if let Some(original) = store
+                .change_author(author.username(), |author| {
+                    author.set_following(false)
+                })
+            {
+                orders.perform_cmd(request::follow::unfollow(
                     model.session.viewer().cloned(),
                     author.username(),
-                    Msg::FollowChangeCompleted,
-                ))
-                .skip();
+                    move |result| Msg::FollowChangeCompleted(original, result),
+                ));
+            }
         },
         Msg::PostCommentClicked(slug) => {
-            let model_comments = &mut model.comments;
-            match model_comments {
-                Status::Loaded((CommentText::Editing(text), _))
-                    if text.is_empty() =>
-                {
+            match (&mut model.comments, model.session.viewer()) {
+                (Status::Loaded((text, _)), _) if text.is_empty() => {
                     orders.skip();
-                }
-                Status::Loaded((CommentText::Editing(text), comments)) => {
+                },
+                (Status::Loaded((text, comment_ids)), Some(viewer)) => {
+                    let pending_id = CommentId::new_pending(&slug);
+                    let article_slug = slug.clone();
+                    let text = take(text);
+                    comment_ids.push_front(store.insert_comment(
+                        Comment::new_pending(
+                            pending_id.clone(),
+                            text.clone(),
+                            viewer.clone(),
+                        ),
+                    ));
                     orders.perform_cmd(request::comment::create(
-                        model.session.viewer().cloned(),
+                        Some(viewer.clone()),
                         &slug,
                         text.clone(),
-                        Msg::PostCommentCompleted,
-                    ));
-                    *model_comments = Status::Loaded((
-                        CommentText::Sending(take(text)),
-                        take(comments),
+                        move |result| {
+                            Msg::PostCommentCompleted(
+                                article_slug,
+                                pending_id,
+                                text,
+                                result,
+                            )
+                        },
                     ));
                 },
                 _ => logger::error(
-                    "Comment can be created only in Editing mode!",
+                    "Comment can be created only by a viewer when comments are loaded!",
                 ),
             }
         },
         Msg::CommentTextEntered(comment_text) => match &mut model.comments {
-            Status::Loaded((CommentText::Editing(text), _)) => {
+            Status::Loaded((text, _)) => {
                 *text = comment_text;
             },
             _ => logger::error(
-                "Comment text can be changed only in Editing mode!",
+                "Comment text can be changed only when comments are loaded!",
             ),
         },
 
-        Msg::LoadArticleCompleted(Ok(article)) => {
-            model.article = Status::Loaded(article)
+        Msg::LoadArticleCompleted(request_id, _)
+        | Msg::LoadCommentsCompleted(request_id, _)
+            if request_id != model.request_id =>
+        {
+            orders.skip();
         },
-        Msg::LoadArticleCompleted(Err(errors)) => {
+        Msg::LoadArticleCompleted(_, Ok(article)) => {
+            model.article = Status::Loaded(store.insert_article(article));
+            notify_if_loaded(model, orders);
+        },
+        Msg::LoadArticleCompleted(_, Err(error)) => {
             model.article = Status::Failed;
-            logger::errors(errors);
+            logger::error(error);
+            notify_if_loaded(model, orders);
         },
 
-        Msg::LoadCommentsCompleted(Ok(comments)) => {
-            model.comments =
-                Status::Loaded((CommentText::Editing("".into()), comments));
+        Msg::LoadCommentsCompleted(_, Ok(comments)) => {
+            let comment_ids = comments
+                .into_iter()
+                .map(|comment| store.insert_comment(comment))
+                .collect();
+            model.comments = Status::Loaded((String::new(), comment_ids));
+            if model.target_comment.is_some() {
+                orders.after_next_render(|_| Msg::TargetCommentRendered);
+            }
+            notify_if_loaded(model, orders);
         },
-        Msg::LoadCommentsCompleted(Err(errors)) => {
+        Msg::LoadCommentsCompleted(_, Err(error)) => {
             model.comments = Status::Failed;
-            logger::errors(errors);
+            logger::error(error);
+            notify_if_loaded(model, orders);
         },
 
         Msg::DeleteArticleCompleted(Ok(())) => {
-            route::go_to(Route::Home, orders);
+            if let Status::Loaded(slug) = &model.article {
+                store.remove_article(slug);
+            }
+            route::go_to(Route::Home(Default::default()), orders);
         },
-        Msg::DeleteArticleCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors
+        Msg::DeleteArticleCompleted(Err(error)) => {
+            logger::error(&error);
+            model.errors = error.into_error_messages()
         },
 
         Msg::DeleteCommentCompleted(Ok(comment_id)) => {
             if let Status::Loaded((_, comments)) = &mut model.comments {
-                comments.retain(|comment| comment.id != comment_id);
+                comments.retain(|id| id != &comment_id);
             }
+            store.remove_comment(&comment_id);
         },
-        Msg::DeleteCommentCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors
+        Msg::DeleteCommentCompleted(Err(error)) => {
+            logger::error(&error);
+            model.errors = error.into_error_messages()
         },
 
-        Msg::FavoriteChangeCompleted(Ok(article)) => {
-            model.article = Status::Loaded(article);
+        Msg::FavoriteChangeCompleted(_, Ok(article)) => {
+            store.insert_article(article);
         },
-        Msg::FavoriteChangeCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors
+        // keep the change, it will be sent once the browser is online
+        Msg::FavoriteChangeCompleted(original, Err(ApiError::Network)) => {
+            orders.send_g_msg(GMsg::MutationQueued(
+                Mutation::new_favorite_change(&original),
+            ));
+        },
+        Msg::FavoriteChangeCompleted(original, Err(error)) => {
+            store.restore_favorited(&original);
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(Toast::new_error(
+                i18n::t("toast.favorite_failed"),
+                error,
+            )));
         },
 
-        Msg::FollowChangeCompleted(Ok(author)) => {
-            if let Status::Loaded(article) = &mut model.article {
-                article.author = author;
-            }
+        Msg::FollowChangeCompleted(_, Ok(author)) => {
+            store.insert_author(author);
         },
-        Msg::FollowChangeCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors
+        Msg::FollowChangeCompleted(original, Err(ApiError::Network)) => {
+            orders.send_g_msg(GMsg::MutationQueued(
+                Mutation::new_follow_change(&original),
+            ));
+        },
+        Msg::FollowChangeCompleted(original, Err(error)) => {
+            store.restore_following(&original);
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(Toast::new_error(
+                i18n::t("toast.follow_failed"),
+                error,
+            )));
         },
 
-        Msg::PostCommentCompleted(Ok(comment)) => {
-            if let Status::Loaded((comment_text, comments)) =
-                &mut model.comments
-            {
-                *comment_text = CommentText::Editing("".into());
-                comments.push_front(comment);
+        Msg::PostCommentCompleted(slug, pending_id, _, Ok(comment)) => {
+            store.remove_comment(&pending_id);
+            let comment_id = store.insert_comment(comment);
+            if let Some((_, comment_ids)) = loaded_comments(model, &slug) {
+                if let Some(id) =
+                    comment_ids.iter_mut().find(|id| **id == pending_id)
+                {
+                    *id = comment_id;
+                }
             }
         },
-        Msg::PostCommentCompleted(Err(errors)) => {
-            if let Status::Loaded((comment_text, _)) = &mut model.comments {
-                if let CommentText::Sending(text) = comment_text {
-                    *comment_text = CommentText::Editing(take(text))
+        // the outbox displays the comment until it's posted
+        Msg::PostCommentCompleted(
+            slug,
+            pending_id,
+            text,
+            Err(ApiError::Network),
+        ) => {
+            store.remove_comment(&pending_id);
+            if let Some((_, comment_ids)) = loaded_comments(model, &slug) {
+                comment_ids.retain(|id| id != &pending_id);
+            }
+            orders.send_g_msg(GMsg::MutationQueued(Mutation::CreateComment(
+                slug, text,
+            )));
+        },
+        Msg::PostCommentCompleted(slug, pending_id, text, Err(error)) => {
+            store.remove_comment(&pending_id);
+            if let Some((comment_text, comment_ids)) =
+                loaded_comments(model, &slug)
+            {
+                comment_ids.retain(|id| id != &pending_id);
+                // don't overwrite a new comment
+                if comment_text.is_empty() {
+                    *comment_text = text;
                 }
             }
-            logger::errors(&errors);
-            model.errors = errors
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(Toast::new_error(
+                i18n::t("toast.comment_failed"),
+                error,
+            )));
         },
 
         Msg::SlowLoadThresholdPassed => {
@@ -318,52 +458,122 @@ pub fn update(
     }
 }
 
+/// The comment text and ids, if comments of the article with `slug`
+/// are loaded - responses may arrive after the page has changed.
+fn loaded_comments<'a>(
+    model: &'a mut Model,
+    slug: &Slug,
+) -> Option<&'a mut (String, VecDeque<CommentId>)> {
+    match (&model.article, &mut model.comments) {
+        (Status::Loaded(article_slug), Status::Loaded(comments))
+            if article_slug == slug =>
+        {
+            Some(comments)
+        },
+        _ => None,
+    }
+}
+
+/// Send `GMsg::PageLoaded` once both the article and comments are loaded.
+fn notify_if_loaded(model: &Model, orders: &mut impl Orders<Msg, GMsg>) {
+    if !model.article.is_loading() && !model.comments.is_loading() {
+        orders.send_g_msg(GMsg::PageLoaded);
+    }
+}
+
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view(model: &Model) -> ViewPage<Msg> {
-    ViewPage::new(title_prefix(&model.article), view_content(model))
+pub fn view<'a>(
+    model: &Model,
+    store: &'a Store,
+    outbox: &Outbox,
+) -> ViewPage<'a, Msg> {
+    let article = match &model.article {
+        Status::Loaded(slug) => store.article(slug),
+        _ => None,
+    };
+    ViewPage::new(title_prefix(article), view_content(model, store, outbox))
 }
 
 // ====== PRIVATE ======
 
-fn title_prefix(article: &Status<Article>) -> impl Into<Cow<str>> {
-    match &article {
-        Status::Loaded(article) => &article.title,
-        _ => "Article",
-    }
+fn title_prefix(article: Option<&Article>) -> impl Into<Cow<'_, str>> {
+    article.map_or(i18n::t("article.title"), |article| &article.title)
 }
 
-fn view_content(model: &Model) -> Node<Msg> {
+fn view_content(model: &Model, store: &Store, outbox: &Outbox) -> Node<Msg> {
     match &model.article {
         Status::Loading => empty![],
         Status::LoadingSlowly => loading::view_icon(),
-        Status::Failed => loading::view_error("article"),
-        Status::Loaded(article) => div![
-            class!["article-page"],
-            view_banner(article, model),
+        Status::Failed => {
+            loading::view_error(i18n::t("loading.article_failed"))
+        },
+        Status::Loaded(slug) => store.article(slug).map_or_else(
+            || loading::view_error(i18n::t("loading.article_failed")),
+            |article| view_article(article, model, store, outbox),
+        ),
+    }
+}
+
+fn view_article(
+    article: &Article,
+    model: &Model,
+    store: &Store,
+    outbox: &Outbox,
+) -> Node<Msg> {
+    div![
+        class!["article-page"],
+        view_banner(article, model),
+        div![
+            class!["container", "page"],
             div![
-                class!["container", "page"],
+                class!["row", "article-content"],
+                view_table_of_contents(article.body.headings()),
                 div![
-                    class!["row", "article-content"],
-                    div![class!["col-md-12"], md!(article.body.as_str())]
-                ],
-                hr![],
-                div![
-                    class!["article-actions"],
-                    view_article_meta(article, model)
-                ],
+                    class!["col-md-12"],
+                    markdown::view(article.body.as_str())
+                ]
+            ],
+            hr![],
+            div![class!["article-actions"], view_article_meta(article, model)],
+            div![
+                class!["row"],
                 div![
-                    class!["row"],
-                    div![
-                        class!["col-xs-12", "col-md-8", "offset-md-2"],
-                        view_form_and_comments(&article.slug, model)
-                    ]
-                ],
-            ]
-        ],
+                    class!["col-xs-12", "col-md-8", "offset-md-2"],
+                    view_form_and_comments(&article.slug, model, store, outbox)
+                ]
+            ],
+        ]
+    ]
+}
+
+fn view_table_of_contents(headings: Vec<Heading>) -> Node<Msg> {
+    if headings.len() < 2 {
+        return empty![];
     }
+    div![
+        class!["col-md-12"],
+        h4![i18n::t("article.contents")],
+        ul![headings.into_iter().map(view_heading_link)],
+        hr![],
+    ]
+}
+
+fn view_heading_link(heading: Heading) -> Node<Msg> {
+    let anchor = heading.anchor;
+    li![
+        style! {"margin-left" => format!("{}em", heading.level - 1)},
+        a![
+            attrs! {At::Href => format!("#{}", anchor)},
+            raw_ev(Ev::Click, move |event| {
+                event.prevent_default();
+                Msg::HeadingClicked(anchor)
+            }),
+            heading.text,
+        ]
+    ]
 }
 
 fn view_banner(article: &Article, model: &Model) -> Node<Msg> {
@@ -382,13 +592,21 @@ fn view_article_meta(article: &Article, model: &Model) -> Node<Msg> {
     div![
         class!["article-meta"],
         a![
-            attrs! {At::Href => Route::Profile(Cow::Borrowed(article.author.username())).to_string()},
+            attrs! {At::Href => Route::Profile(Cow::Borrowed(article.author.username()), Default::default()).to_string()},
             img![attrs! {At::Src => article.author.profile().avatar.src()}]
         ],
         div![
             class!["info"],
             author::view(article.author.username()),
-            span![class!["date"], timestamp::view(&article.created_at)]
+            span![
+                class!["date"],
+                timestamp::view(&article.created_at),
+                timestamp::view_edited(
+                    &article.created_at,
+                    &article.updated_at
+                )
+            ],
+            markdown::view_reading_time(&article.body),
         ],
         view_buttons(article, model),
     ]
@@ -396,15 +614,23 @@ fn view_article_meta(article: &Article, model: &Model) -> Node<Msg> {
 
 // ------ view form and comments
 
-fn view_form_and_comments(slug: &Slug, model: &Model) -> Vec<Node<Msg>> {
+fn view_form_and_comments(
+    slug: &Slug,
+    model: &Model,
+    store: &Store,
+    outbox: &Outbox,
+) -> Vec<Node<Msg>> {
     match &model.comments {
         Status::Loading => vec![],
         Status::LoadingSlowly => vec![loading::view_icon()],
-        Status::Failed => vec![loading::view_error("comments")],
-        Status::Loaded((comment_text, comments)) => {
+        Status::Failed => {
+            vec![loading::view_error(i18n::t("loading.comments_failed"))]
+        },
+        Status::Loaded((comment_text, comment_ids)) => {
             vec![view_comment_form(slug.clone(), comment_text, model)]
                 .into_iter()
-                .chain(view_comments(slug, comments))
+                .chain(view_queued_comments(slug, model, outbox))
+                .chain(view_comments(slug, model, comment_ids, store))
                 .collect()
         },
     }
@@ -412,70 +638,108 @@ fn view_form_and_comments(slug: &Slug, model: &Model) -> Vec<Node<Msg>> {
 
 fn view_comment_form(
     slug: Slug,
-    comment_text: &CommentText,
+    comment_text: &str,
     model: &Model,
 ) -> Node<Msg> {
     match model.session.viewer() {
         None => p![
-            a!["Sign in", attrs! {At::Href => Route::Login.to_string()}],
-            " or ",
-            a!["Sign up", attrs! {At::Href => Route::Register.to_string()}],
-            " to comment."
+            a![
+                i18n::t("auth.sign_in"),
+                attrs! {At::Href => Route::Login(None).to_string()}
+            ],
+            i18n::t("article.or"),
+            a![
+                i18n::t("auth.sign_up"),
+                attrs! {At::Href => Route::Register.to_string()}
+            ],
+            i18n::t("article.to_comment")
         ],
-        Some(viewer) => {
-            let (comment_text, post_comment_disabled) = match comment_text {
-                CommentText::Editing(text) => (text, false),
-                CommentText::Sending(text) => (text, true),
-            };
-
-            form![
-                class!["card", "comment-form"],
-                raw_ev(Ev::Submit, |event| {
-                    event.prevent_default();
-                    Msg::PostCommentClicked(slug)
-                }),
-                div![
-                    class!["card-block"],
-                    textarea![
-                        class!["form-control"],
-                        input_ev(Ev::Input, Msg::CommentTextEntered),
-                        attrs! {
-                            At::Rows => 3,
-                            At::Placeholder => "Write a comment...",
-                            At::Value => comment_text,
-                        }
-                    ]
+        Some(viewer) => form![
+            class!["card", "comment-form"],
+            raw_ev(Ev::Submit, |event| {
+                event.prevent_default();
+                Msg::PostCommentClicked(slug)
+            }),
+            div![
+                class!["card-block"],
+                textarea![
+                    class!["form-control"],
+                    input_ev(Ev::Input, Msg::CommentTextEntered),
+                    attrs! {
+                        At::Rows => 3,
+                        At::Placeholder => i18n::t("article.comment_placeholder"),
+                        At::Value => comment_text,
+                    }
+                ]
+            ],
+            div![
+                class!["card-footer"],
+                img![
+                    class!["comment-author-img"],
+                    attrs! {At::Src => viewer.avatar().src()}
                 ],
-                div![
-                    class!["card-footer"],
-                    img![
-                        class!["comment-author-img"],
-                        attrs! {At::Src => viewer.avatar().src()}
-                    ],
-                    button![
-                        class!["btn", "btn-sm", "btn-primary"],
-                        attrs! {At::Disabled => post_comment_disabled.as_at_value()},
-                        "Post Comment"
-                    ]
+                button![
+                    class!["btn", "btn-sm", "btn-primary"],
+                    i18n::t("article.post_comment")
                 ]
             ]
-        },
+        ],
     }
 }
 
-fn view_comments(slug: &Slug, comments: &VecDeque<Comment>) -> Vec<Node<Msg>> {
-    comments.iter().map(|comment| view_comment(slug, comment)).collect()
+fn view_comments(
+    slug: &Slug,
+    model: &Model,
+    comment_ids: &VecDeque<CommentId>,
+    store: &Store,
+) -> Vec<Node<Msg>> {
+    comment_ids
+        .iter()
+        .filter_map(|comment_id| store.comment(comment_id))
+        .map(|comment| view_comment(slug, model, comment))
+        .collect()
+}
+
+/// Comments waiting in the outbox are displayed as pending, the newest first.
+fn view_queued_comments(
+    slug: &Slug,
+    model: &Model,
+    outbox: &Outbox,
+) -> Vec<Node<Msg>> {
+    model.session.viewer().map_or_else(Vec::new, |viewer| {
+        outbox
+            .comments(slug)
+            .rev()
+            .enumerate()
+            .map(|(index, text)| {
+                let comment = Comment::new_pending(
+                    CommentId::new_queued(slug, index),
+                    text.to_owned(),
+                    viewer.clone(),
+                );
+                view_comment(slug, model, &comment)
+            })
+            .collect()
+    })
 }
 
-fn view_comment(slug: &Slug, comment: &Comment) -> Node<Msg> {
+fn view_comment(slug: &Slug, model: &Model, comment: &Comment) -> Node<Msg> {
     div![
         class!["card"],
+        attrs! {At::Id => comment.id.anchor()},
+        if comment.id.is_pending() {
+            style! {"opacity" => 0.5}
+        } else if model.target_comment.as_ref() == Some(&comment.id) {
+            style! {"box-shadow" => "0 0 0 2px #5cb85c"}
+        } else {
+            style! {}
+        },
         div![class!["card-block"], p![class!["card-text"], comment.body]],
         div![
             class!["card-footer"],
             a![
                 class!["comment-author"],
-                attrs! {At::Href => Route::Profile(Cow::Borrowed(comment.author.username())).to_string()},
+                attrs! {At::Href => Route::Profile(Cow::Borrowed(comment.author.username()), Default::default()).to_string()},
                 img![
                     class!["comment-author-img"],
                     attrs! {At::Src => comment.author.profile().avatar.src()}
@@ -484,11 +748,19 @@ fn view_comment(slug: &Slug, comment: &Comment) -> Node<Msg> {
             raw!("&nbsp;"),
             a![
                 class!["comment-author"],
-                attrs! {At::Href => Route::Profile(Cow::Borrowed(comment.author.username())).to_string()},
+                attrs! {At::Href => Route::Profile(Cow::Borrowed(comment.author.username()), Default::default()).to_string()},
                 comment.author.username().to_string()
             ],
-            span![class!["date-posted"], timestamp::view(&comment.created_at)],
-            view_delete_comment_button(slug, comment)
+            span![
+                class!["date-posted"],
+                timestamp::view(&comment.created_at),
+                timestamp::view_edited(
+                    &comment.created_at,
+                    &comment.updated_at
+                )
+            ],
+            view_delete_comment_button(slug, comment),
+            view_copy_comment_link_button(slug, model, comment),
         ]
     ]
 }
@@ -530,7 +802,13 @@ fn view_favorite_button(article: &Article) -> Node<Msg> {
             simple_ev(Ev::Click, Msg::FavoriteClicked(article.slug.clone())),
             i![
                 class!["ion-heart"],
-                format!(" Favorite Article ({})", article.favorites_count),
+                format!(
+                    " {}",
+                    i18n::t_with(
+                        "article.favorite",
+                        &[("count", &article.favorites_count)]
+                    )
+                ),
             ]
         ]
     } else {
@@ -539,7 +817,13 @@ fn view_favorite_button(article: &Article) -> Node<Msg> {
             simple_ev(Ev::Click, Msg::UnfavoriteClicked(article.slug.clone())),
             i![
                 class!["ion-heart"],
-                format!(" Favorite Article ({})", article.favorites_count),
+                format!(
+                    " {}",
+                    i18n::t_with(
+                        "article.favorite",
+                        &[("count", &article.favorites_count)]
+                    )
+                ),
             ]
         ]
     }
@@ -547,7 +831,7 @@ fn view_favorite_button(article: &Article) -> Node<Msg> {
 
 fn view_delete_comment_button(slug: &Slug, comment: &Comment) -> Node<Msg> {
     match comment.author {
-        Author::IsViewer(..) => span![
+        Author::IsViewer(..) if !comment.id.is_pending() => span![
             class!["mod-options"],
             i![
                 class!["ion-trash-a"],
@@ -561,12 +845,42 @@ fn view_delete_comment_button(slug: &Slug, comment: &Comment) -> Node<Msg> {
     }
 }
 
+fn view_copy_comment_link_button(
+    slug: &Slug,
+    model: &Model,
+    comment: &Comment,
+) -> Node<Msg> {
+    if comment.id.is_pending() {
+        return empty![];
+    }
+    let copied = model.copied_comment.as_ref() == Some(&comment.id);
+    span![
+        class!["mod-options"],
+        attrs! {At::Title => i18n::t("article.copy_link")},
+        style! {"cursor" => "pointer"},
+        simple_ev(
+            Ev::Click,
+            Msg::CopyCommentLinkClicked(slug.clone(), comment.id.clone())
+        ),
+        i![class![if copied {
+            "ion-checkmark"
+        } else {
+            "ion-link"
+        }]],
+        if copied {
+            format!(" {}", i18n::t("article.link_copied"))
+        } else {
+            String::new()
+        }
+    ]
+}
+
 fn view_edit_button(slug: Slug) -> Node<Msg> {
     a![
         class!["btn", "btn-outline-secondary", "btn-sm"],
         attrs! {At::Href => Route::EditArticle(slug).to_string()},
         i![class!["ion-edit"],],
-        " Edit Article",
+        format!(" {}", i18n::t("article.edit")),
     ]
 }
 
@@ -575,6 +889,6 @@ fn view_delete_button(slug: Slug) -> Node<Msg> {
         class!["btn", "btn-outline-danger", "btn-sm"],
         simple_ev(Ev::Click, Msg::DeleteArticleClicked(slug)),
         i![class!["ion-trash-a"]],
-        " Delete Article",
+        format!(" {}", i18n::t("article.delete")),
     ]
 }
@@ -2,16 +2,21 @@ use super::ViewPage;
 use crate::{
     entity::{
         form::article_editor::{Field, Form, Problem},
-        Article, Slug,
+        markdown, ApiError, Article, Slug, Viewer,
     },
     helper::take,
-    loading, logger, request,
+    i18n, loading, logger,
+    outbox::Mutation,
+    request::{self, RequestId},
     route::{self, Route},
-    GMsg, Session,
+    storage, GMsg, Session,
 };
+use gloo_timers::future::TimeoutFuture;
 use seed::prelude::*;
 use std::borrow::Cow;
 
+const DRAFT_SAVE_DELAY_MS: u32 = 1000;
+
 // ------ ------
 //     Model
 // ------ ------
@@ -21,6 +26,11 @@ use std::borrow::Cow;
 #[derive(Default)]
 pub struct Model {
     session: Session,
+    request_id: RequestId,
+    /// Number of changes - only the last one saves the draft.
+    change_count: usize,
+    draft_restored: bool,
+    body_tab: BodyTab,
     status: Status,
 }
 
@@ -36,6 +46,15 @@ impl From<Model> for Session {
     }
 }
 
+// ------ BodyTab ------
+
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+pub enum BodyTab {
+    #[default]
+    Write,
+    Preview,
+}
+
 // ------ Status ------
 
 enum Status {
@@ -75,8 +94,11 @@ impl Default for Status {
 // ------ ------
 
 pub fn init_new(session: Session) -> Model {
+    let draft = load_draft(&session, None);
     Model {
         session,
+        draft_restored: draft.is_some(),
+        status: Status::EditingNew(Vec::new(), draft.unwrap_or_default()),
         ..Model::default()
     }
 }
@@ -86,16 +108,20 @@ pub fn init_edit(
     slug: Slug,
     orders: &mut impl Orders<Msg, GMsg>,
 ) -> Model {
+    let request_id = RequestId::new();
+
     orders
         .perform_cmd(loading::notify_on_slow_load(Msg::SlowLoadThresholdPassed))
         .perform_cmd(request::article::load_for_editor(
             session.viewer().cloned(),
             slug.clone(),
-            Msg::ArticleLoadCompleted,
+            move |result| Msg::ArticleLoadCompleted(request_id, result),
         ));
     Model {
         session,
+        request_id,
         status: Status::Loading(slug),
+        ..Model::default()
     }
 }
 
@@ -111,7 +137,7 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            route::go_to(Route::Home(Default::default()), orders);
         },
         _ => (),
     }
@@ -125,13 +151,15 @@ pub fn sink(
 pub enum Msg {
     FieldChanged(Field),
     FormSubmitted,
-    CreateCompleted(Result<Article, Vec<Problem>>),
-    EditCompleted(Result<Article, Vec<Problem>>),
-    ArticleLoadCompleted(Result<Article, (Slug, Vec<Problem>)>),
+    CreateCompleted(Result<Article, ApiError>),
+    EditCompleted(Result<Article, ApiError>),
+    ArticleLoadCompleted(RequestId, Result<Article, (Slug, ApiError)>),
+    DraftSaveDelayPassed(usize),
+    BodyTabClicked(BodyTab),
     SlowLoadThresholdPassed,
 }
 
-#[allow(clippy::match_same_arms)]
+#[allow(clippy::match_same_arms, clippy::too_many_lines)]
 pub fn update(
     msg: Msg,
     model: &mut Model,
@@ -141,6 +169,8 @@ pub fn update(
         Msg::FieldChanged(field) => match &mut model.status {
             Status::Editing(_, _, form) | Status::EditingNew(_, form) => {
                 form.upsert_field(field);
+                model.change_count += 1;
+                orders.perform_cmd(notify_on_draft_save(model.change_count));
             },
             _ => logger::error(
                 "Can't edit the form, status has to be Editing or EditingNew!",
@@ -184,30 +214,103 @@ pub fn update(
             ),
         },
         Msg::CreateCompleted(Ok(article)) => {
-            route::go_to(Route::Article(article.slug), orders)
+            remove_draft(&model.session, None);
+            route::go_to(Route::Article(article.slug, None), orders)
+        },
+        // the article will be created once the browser is online
+        Msg::CreateCompleted(Err(ApiError::Network)) => {
+            if let Status::Creating(form) = &mut model.status {
+                match form.trim_fields().validate() {
+                    Ok(valid_form) => {
+                        orders.send_g_msg(GMsg::MutationQueued(
+                            Mutation::CreateArticle(valid_form),
+                        ));
+                        // the queued mutation contains the changes
+                        remove_draft(&model.session, None);
+                        route::go_to(Route::Home(Default::default()), orders);
+                    },
+                    Err(problems) => {
+                        model.status = Status::EditingNew(problems, take(form));
+                    },
+                }
+            }
         },
-        Msg::CreateCompleted(Err(problems)) => {
+        Msg::CreateCompleted(Err(error)) => {
             if let Status::Creating(form) = &mut model.status {
-                model.status = Status::EditingNew(problems, take(form))
+                model.status =
+                    Status::EditingNew(error.into_problems(), take(form))
             }
         },
         Msg::EditCompleted(Ok(article)) => {
-            route::go_to(Route::Article(article.slug), orders)
+            if let Status::Saving(slug, _) = &model.status {
+                remove_draft(&model.session, Some(slug));
+            }
+            route::go_to(Route::Article(article.slug, None), orders)
         },
-        Msg::EditCompleted(Err(problems)) => {
+        Msg::EditCompleted(Err(ApiError::Network)) => {
             if let Status::Saving(slug, form) = &mut model.status {
-                model.status = Status::Editing(take(slug), problems, take(form))
+                match form.trim_fields().validate() {
+                    Ok(valid_form) => {
+                        orders.send_g_msg(GMsg::MutationQueued(
+                            Mutation::UpdateArticle(slug.clone(), valid_form),
+                        ));
+                        remove_draft(&model.session, Some(slug));
+                        route::go_to(Route::Article(take(slug), None), orders);
+                    },
+                    Err(problems) => {
+                        model.status =
+                            Status::Editing(take(slug), problems, take(form));
+                    },
+                }
+            }
+        },
+        Msg::EditCompleted(Err(error)) => {
+            if let Status::Saving(slug, form) = &mut model.status {
+                model.status = Status::Editing(
+                    take(slug),
+                    error.into_problems(),
+                    take(form),
+                )
             }
         },
-        Msg::ArticleLoadCompleted(Ok(article)) => {
+        Msg::ArticleLoadCompleted(request_id, _)
+            if request_id != model.request_id =>
+        {
+            orders.skip();
+        },
+        Msg::ArticleLoadCompleted(_, Ok(article))
+            if Some(article.author.username())
+                != model.session.viewer().map(Viewer::username) =>
+        {
+            // another user can only read the article
+            let route = if model.session.viewer().is_some() {
+                Route::Article(article.slug, None)
+            } else {
+                Route::Login(Some(Box::new(Route::EditArticle(article.slug))))
+            };
+            route::redirect_to(route, orders);
+        },
+        Msg::ArticleLoadCompleted(_, Ok(article)) => {
+            let slug = article.slug.clone();
+            let draft = load_draft(&model.session, Some(&slug));
+            model.draft_restored = draft.is_some();
             model.status = Status::Editing(
-                article.slug.clone(),
+                slug,
                 vec![],
-                article.into_form(),
+                draft.unwrap_or_else(|| article.into_form()),
             );
         },
-        Msg::ArticleLoadCompleted(Err((slug, problems))) => {
-            model.status = Status::LoadingFailed(slug, problems)
+        Msg::ArticleLoadCompleted(_, Err((slug, error))) => {
+            model.status = Status::LoadingFailed(slug, error.into_problems())
+        },
+        Msg::DraftSaveDelayPassed(change_count) => {
+            if change_count == model.change_count {
+                save_draft(model);
+            }
+            orders.skip();
+        },
+        Msg::BodyTabClicked(body_tab) => {
+            model.body_tab = body_tab;
         },
         Msg::SlowLoadThresholdPassed => {
             if let Status::Loading(slug) = &mut model.status {
@@ -217,6 +320,49 @@ pub fn update(
     }
 }
 
+async fn notify_on_draft_save(change_count: usize) -> Result<Msg, Msg> {
+    TimeoutFuture::new(DRAFT_SAVE_DELAY_MS).await;
+    Ok(Msg::DraftSaveDelayPassed(change_count))
+}
+
+/// Store the edited form immediately, without waiting for the debounce
+/// (e.g. before the page is replaced because the session has expired).
+pub fn save_draft(model: &Model) {
+    match &model.status {
+        Status::Editing(slug, _, form) => {
+            store_draft(&model.session, Some(slug), form)
+        },
+        Status::EditingNew(_, form) => store_draft(&model.session, None, form),
+        // the form is being saved
+        _ => (),
+    }
+}
+
+fn load_draft(session: &Session, slug: Option<&Slug>) -> Option<Form> {
+    let viewer = session.viewer()?;
+    storage::load_draft(viewer.username(), slug).unwrap_or_else(|error| {
+        logger::error(error);
+        None
+    })
+}
+
+fn store_draft(session: &Session, slug: Option<&Slug>, form: &Form) {
+    if let Some(viewer) = session.viewer() {
+        if let Err(error) = storage::store_draft(viewer.username(), slug, form)
+        {
+            logger::error(error);
+        }
+    }
+}
+
+fn remove_draft(session: &Session, slug: Option<&Slug>) {
+    if let Some(viewer) = session.viewer() {
+        if let Err(error) = storage::remove_draft(viewer.username(), slug) {
+            logger::error(error);
+        }
+    }
+}
+
 // ------ ------
 //     View
 // ------ ------
@@ -229,8 +375,10 @@ pub fn view(model: &Model) -> ViewPage<Msg> {
 
 fn title_prefix(slug: Option<&Slug>) -> impl Into<Cow<str>> {
     match slug {
-        Some(slug) => format!("Edit Article - {}", slug.as_str()),
-        None => "New Article".into(),
+        Some(slug) => {
+            i18n::t_with("editor.title_edit", &[("slug", &slug.as_str())])
+        },
+        None => i18n::t("editor.title_new").into(),
     }
 }
 
@@ -252,7 +400,7 @@ fn view_content(model: &Model) -> Node<Msg> {
                     if model.session.viewer().is_some() {
                         view_authenticated(model)
                     } else {
-                        vec![div!["Sign in to edit this article."]]
+                        vec![div![i18n::t("editor.sign_in")]]
                     }
                 ]
             ]
@@ -265,48 +413,81 @@ fn view_authenticated(model: &Model) -> Vec<Node<Msg>> {
         Status::Loading(_) => vec![],
         Status::LoadingSlowly(_) => vec![loading::view_icon()],
         Status::LoadingFailed(_, problems) => {
-            vec![view_problems(problems), loading::view_error("article")]
+            vec![
+                view_problems(problems),
+                loading::view_error(i18n::t("loading.article_failed")),
+            ]
         },
         Status::Saving(_, form) => vec![view_form(
             form,
+            model.body_tab,
             view_save_button(SaveButton::UpdateArticle, true),
         )],
         Status::Editing(_, problems, form) => vec![
             view_problems(problems),
-            view_form(form, view_save_button(SaveButton::UpdateArticle, false)),
+            view_draft_notice(model.draft_restored),
+            view_form(
+                form,
+                model.body_tab,
+                view_save_button(SaveButton::UpdateArticle, false),
+            ),
         ],
         Status::EditingNew(problems, form) => vec![
             view_problems(problems),
-            view_form(form, view_save_button(SaveButton::CreateArticle, false)),
+            view_draft_notice(model.draft_restored),
+            view_form(
+                form,
+                model.body_tab,
+                view_save_button(SaveButton::CreateArticle, false),
+            ),
         ],
         Status::Creating(form) => vec![view_form(
             form,
+            model.body_tab,
             view_save_button(SaveButton::CreateArticle, true),
         )],
     }
 }
 
+fn view_draft_notice(draft_restored: bool) -> Node<Msg> {
+    if draft_restored {
+        p![
+            i18n::t("editor.draft_restored"),
+            a![
+                attrs! {At::Href => Route::Drafts.to_string()},
+                i18n::t("editor.all_drafts")
+            ]
+        ]
+    } else {
+        empty![]
+    }
+}
+
 fn view_save_button(type_: SaveButton, disabled: bool) -> Node<Msg> {
     button![
         class!["btn", "btn-lg", "btn-primary", "pull-xs-right"],
         simple_ev(Ev::Click, Msg::FormSubmitted),
         attrs! {At::Type => "button", At::Disabled => disabled.as_at_value()},
         match type_ {
-            SaveButton::CreateArticle => "Publish Article",
-            SaveButton::UpdateArticle => "Update Article",
+            SaveButton::CreateArticle => i18n::t("editor.publish"),
+            SaveButton::UpdateArticle => i18n::t("editor.update"),
         }
     ]
 }
 
 // ------ view form ------
 
-fn view_form(form: &Form, save_button: Node<Msg>) -> Node<Msg> {
+fn view_form(
+    form: &Form,
+    body_tab: BodyTab,
+    save_button: Node<Msg>,
+) -> Node<Msg> {
     form![
         raw_ev(Ev::Submit, |event| {
             event.prevent_default();
             Msg::FormSubmitted
         }),
-        form.iter_fields().map(view_fieldset),
+        form.iter_fields().map(|field| view_fieldset(field, body_tab)),
         save_button,
     ]
 }
@@ -318,7 +499,7 @@ fn view_problems(problems: &[Problem]) -> Node<Msg> {
     ]
 }
 
-fn view_fieldset(field: &Field) -> Node<Msg> {
+fn view_fieldset(field: &Field, body_tab: BodyTab) -> Node<Msg> {
     match field {
         Field::Title(value) => fieldset![
             class!["form-group"],
@@ -326,7 +507,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Article Title",
+                    At::Placeholder => i18n::t("editor.title_placeholder"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -340,7 +521,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "What's this article about?",
+                    At::Placeholder => i18n::t("editor.description_placeholder"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -350,17 +531,29 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
         ],
         Field::Body(value) => fieldset![
             class!["form-group"],
-            textarea![
-                class!["form-control"],
-                attrs! {
-                    At::Rows => 8,
-                    At::Placeholder => "Write your article (in markdown)",
-                },
-                value,
-                input_ev(Ev::Input, |new_value| Msg::FieldChanged(
-                    Field::Body(new_value)
-                )),
-            ]
+            view_body_tabs(body_tab),
+            match body_tab {
+                BodyTab::Write => textarea![
+                    class!["form-control"],
+                    attrs! {
+                        At::Rows => 8,
+                        At::Placeholder => i18n::t("editor.body_placeholder"),
+                    },
+                    value,
+                    input_ev(Ev::Input, |new_value| Msg::FieldChanged(
+                        Field::Body(new_value)
+                    )),
+                ],
+                BodyTab::Preview => div![
+                    class!["form-control"],
+                    style! {"height" => "auto", "min-height" => "12rem"},
+                    if value.trim().is_empty() {
+                        vec![plain![i18n::t("editor.nothing_to_preview")]]
+                    } else {
+                        markdown::view(value)
+                    }
+                ],
+            }
         ],
         Field::Tags(value) => fieldset![
             class!["form-group"],
@@ -368,7 +561,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Enter tags",
+                    At::Placeholder => i18n::t("editor.tags_placeholder"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -379,3 +572,21 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
         ],
     }
 }
+
+fn view_body_tabs(body_tab: BodyTab) -> Node<Msg> {
+    use crate::entity::article::feed::{view_tabs, Tab};
+
+    let tab = |title, tab_body_tab| {
+        let tab = Tab::new(title, Msg::BodyTabClicked(tab_body_tab));
+        if tab_body_tab == body_tab {
+            tab.activate()
+        } else {
+            tab
+        }
+    };
+
+    view_tabs(vec![
+        tab(i18n::t("editor.write"), BodyTab::Write),
+        tab(i18n::t("editor.preview"), BodyTab::Preview),
+    ])
+}
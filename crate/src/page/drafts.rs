@@ -0,0 +1,153 @@
+use super::ViewPage;
+use crate::{
+    entity::Slug,
+    i18n, logger,
+    route::{self, Route},
+    storage::{self, Draft},
+    GMsg, Session,
+};
+use seed::prelude::*;
+
+// ------ ------
+//     Model
+// ------ ------
+
+#[derive(Default)]
+pub struct Model {
+    session: Session,
+    drafts: Vec<Draft>,
+}
+
+impl Model {
+    pub const fn session(&self) -> &Session {
+        &self.session
+    }
+}
+
+impl From<Model> for Session {
+    fn from(model: Model) -> Self {
+        model.session
+    }
+}
+
+// ------ ------
+//     Init
+// ------ ------
+
+pub fn init(session: Session) -> Model {
+    Model {
+        drafts: load_drafts(&session),
+        session,
+    }
+}
+
+// ------ ------
+//     Sink
+// ------ ------
+
+pub fn sink(
+    g_msg: GMsg,
+    model: &mut Model,
+    orders: &mut impl Orders<Msg, GMsg>,
+) {
+    match g_msg {
+        GMsg::SessionChanged(session) => {
+            model.session = session;
+            route::go_to(Route::Home(Default::default()), orders);
+        },
+        _ => (),
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+#[derive(Clone)]
+pub enum Msg {
+    DiscardClicked(Option<Slug>),
+}
+
+pub fn update(msg: Msg, model: &mut Model) {
+    match msg {
+        Msg::DiscardClicked(slug) => {
+            if let Some(viewer) = model.session.viewer() {
+                if let Err(error) =
+                    storage::remove_draft(viewer.username(), slug.as_ref())
+                {
+                    logger::error(error);
+                }
+            }
+            model.drafts = load_drafts(&model.session);
+        },
+    }
+}
+
+fn load_drafts(session: &Session) -> Vec<Draft> {
+    let viewer = match session.viewer() {
+        Some(viewer) => viewer,
+        None => return Vec::new(),
+    };
+    storage::load_drafts(viewer.username()).unwrap_or_else(|error| {
+        logger::error(error);
+        Vec::new()
+    })
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view<'a>(model: &Model) -> ViewPage<'a, Msg> {
+    ViewPage::new(i18n::t("nav.drafts"), view_content(model))
+}
+
+// ====== PRIVATE ======
+
+fn view_content(model: &Model) -> Node<Msg> {
+    div![
+        class!["container", "page"],
+        div![
+            class!["row"],
+            div![
+                class!["col-md-10", "offset-md-1", "col-xs-12"],
+                h1![i18n::t("nav.drafts")],
+                if model.drafts.is_empty() {
+                    vec![div![i18n::t("drafts.empty")]]
+                } else {
+                    model.drafts.iter().map(view_draft).collect()
+                }
+            ]
+        ]
+    ]
+}
+
+fn view_draft(draft: &Draft) -> Node<Msg> {
+    let route =
+        draft.slug.clone().map_or(Route::NewArticle, Route::EditArticle);
+    let title = draft.form.title();
+
+    div![
+        class!["article-preview"],
+        a![
+            class!["preview-link"],
+            attrs! {At::Href => route.to_string()},
+            h1![if title.is_empty() {
+                i18n::t("drafts.untitled")
+            } else {
+                title
+            }],
+            span![if draft.slug.is_some() {
+                i18n::t("drafts.published_changes")
+            } else {
+                i18n::t("drafts.new_article")
+            }],
+        ],
+        button![
+            class!["btn", "btn-sm", "btn-outline-danger", "pull-xs-right"],
+            simple_ev(Ev::Click, Msg::DiscardClicked(draft.slug.clone())),
+            i![class!["ion-trash-a"]],
+            format!(" {}", i18n::t("drafts.discard")),
+        ]
+    ]
+}
@@ -2,24 +2,30 @@ use super::ViewPage;
 use crate::{
     entity::{
         article::{self, Article},
-        ErrorMessage, PageNumber, PaginatedList, Tag, Viewer,
+        ApiError, PageNumber, PaginatedList, Tag, Viewer,
     },
-    loading, logger, page, request, GMsg, Session,
+    i18n, loading, logger, page,
+    request::{self, RequestId},
+    route::{self, HomeFeed, HomeQuery, Route},
+    store::Store,
+    GMsg, Session,
 };
 use seed::prelude::*;
-use std::future::Future;
 
 fn fetch_feed(
     viewer: Option<Viewer>,
     selected_feed: &SelectedFeed,
     page_number: PageNumber,
-) -> impl Future<Output = Result<Msg, Msg>> {
+    request_id: RequestId,
+    orders: &mut impl Orders<Msg, GMsg>,
+) {
     request::feed::load_for_home(
+        orders,
         viewer,
         selected_feed,
         page_number,
-        Msg::FeedLoadCompleted,
-    )
+        move |result| Msg::FeedLoadCompleted(request_id, result),
+    );
 }
 
 // ------ ------
@@ -33,6 +39,7 @@ pub struct Model {
     session: Session,
     selected_feed: SelectedFeed,
     feed_page: PageNumber,
+    feed_request_id: RequestId,
     tags: Status<Vec<Tag>>,
     feed: Status<article::feed::Model>,
 }
@@ -41,6 +48,17 @@ impl Model {
     pub const fn session(&self) -> &Session {
         &self.session
     }
+
+    fn query(&self) -> HomeQuery {
+        HomeQuery {
+            feed: Some(match &self.selected_feed {
+                SelectedFeed::Your(_) => HomeFeed::Your,
+                SelectedFeed::Global => HomeFeed::Global,
+                SelectedFeed::Tag(tag) => HomeFeed::Tag(tag.clone()),
+            }),
+            page: self.feed_page,
+        }
+    }
 }
 
 impl From<Model> for Session {
@@ -83,24 +101,41 @@ impl<'a> Default for SelectedFeed {
 //     Init
 // ------ ------
 
-pub fn init(session: Session, orders: &mut impl Orders<Msg, GMsg>) -> Model {
-    let selected_feed = session
-        .viewer()
-        .cloned()
-        .map_or_else(SelectedFeed::default, SelectedFeed::Your);
-
-    orders
-        .perform_cmd(loading::notify_on_slow_load(Msg::SlowLoadThresholdPassed))
-        .perform_cmd(request::tag::load_list(Msg::TagsLoadCompleted))
-        .perform_cmd(fetch_feed(
-            session.viewer().cloned(),
-            &selected_feed,
-            PageNumber::default(),
-        ));
+pub fn init(
+    session: Session,
+    query: HomeQuery,
+    orders: &mut impl Orders<Msg, GMsg>,
+) -> Model {
+    let viewer_feed = || {
+        session
+            .viewer()
+            .cloned()
+            .map_or_else(SelectedFeed::default, SelectedFeed::Your)
+    };
+    let selected_feed = match query.feed {
+        None | Some(HomeFeed::Your) => viewer_feed(),
+        Some(HomeFeed::Global) => SelectedFeed::Global,
+        Some(HomeFeed::Tag(tag)) => SelectedFeed::Tag(tag),
+    };
+    let feed_request_id = RequestId::new();
+
+    orders.perform_cmd(loading::notify_on_slow_load(
+        Msg::SlowLoadThresholdPassed,
+    ));
+    request::tag::load_list(orders, Msg::TagsLoadCompleted);
+    fetch_feed(
+        session.viewer().cloned(),
+        &selected_feed,
+        query.page,
+        feed_request_id,
+        orders,
+    );
 
     Model {
         session,
         selected_feed,
+        feed_page: query.page,
+        feed_request_id,
         ..Model::default()
     }
 }
@@ -109,10 +144,15 @@ pub fn init(session: Session, orders: &mut impl Orders<Msg, GMsg>) -> Model {
 //     Sink
 // ------ ------
 
-pub fn sink(g_msg: GMsg, model: &mut Model) {
+pub fn sink(
+    g_msg: GMsg,
+    model: &mut Model,
+    orders: &mut impl Orders<Msg, GMsg>,
+) {
     match g_msg {
         GMsg::SessionChanged(session) => {
-            model.session = session;
+            // the store has been cleared and the feeds depend on the viewer
+            *model = init(session, model.query(), orders);
         },
         _ => (),
     }
@@ -128,8 +168,8 @@ pub enum Msg {
     TagClicked(Tag),
     TabClicked(SelectedFeed),
     FeedPageClicked(PageNumber),
-    FeedLoadCompleted(Result<PaginatedList<Article>, Vec<ErrorMessage>>),
-    TagsLoadCompleted(Result<Vec<Tag>, Vec<ErrorMessage>>),
+    FeedLoadCompleted(RequestId, Result<PaginatedList<Article>, ApiError>),
+    TagsLoadCompleted(Result<Vec<Tag>, ApiError>),
     FeedMsg(article::feed::Msg),
     SlowLoadThresholdPassed,
 }
@@ -137,57 +177,55 @@ pub enum Msg {
 pub fn update(
     msg: Msg,
     model: &mut Model,
+    store: &mut Store,
     orders: &mut impl Orders<Msg, GMsg>,
 ) {
     match msg {
         Msg::TagClicked(tag) => {
             model.selected_feed = SelectedFeed::Tag(tag);
             model.feed_page = PageNumber::default();
-            orders.perform_cmd(fetch_feed(
-                model.session.viewer().cloned(),
-                &model.selected_feed,
-                model.feed_page,
-            ));
+            change_feed(model, orders);
         },
         Msg::TabClicked(selected_feed) => {
             model.selected_feed = selected_feed;
             model.feed_page = PageNumber::default();
-            orders.perform_cmd(fetch_feed(
-                model.session.viewer().cloned(),
-                &model.selected_feed,
-                model.feed_page,
-            ));
+            change_feed(model, orders);
         },
         Msg::FeedPageClicked(page_number) => {
             model.feed_page = page_number;
-            orders.perform_cmd(fetch_feed(
-                model.session.viewer().cloned(),
-                &model.selected_feed,
-                model.feed_page,
-            ));
+            change_feed(model, orders);
             page::scroll_to_top()
         },
-        Msg::FeedLoadCompleted(Ok(paginated_list)) => {
+        Msg::FeedLoadCompleted(request_id, _)
+            if request_id != model.feed_request_id =>
+        {
+            orders.skip();
+        },
+        Msg::FeedLoadCompleted(_, Ok(paginated_list)) => {
             model.feed = Status::Loaded(article::feed::init(
                 model.session.clone(),
                 paginated_list,
+                store,
             ));
+            orders.send_g_msg(GMsg::PageLoaded);
         },
-        Msg::FeedLoadCompleted(Err(errors)) => {
+        Msg::FeedLoadCompleted(_, Err(error)) => {
             model.feed = Status::Failed;
-            logger::errors(errors);
+            logger::error(error);
+            orders.send_g_msg(GMsg::PageLoaded);
         },
         Msg::TagsLoadCompleted(Ok(tags)) => {
             model.tags = Status::Loaded(tags);
         },
-        Msg::TagsLoadCompleted(Err(errors)) => {
+        Msg::TagsLoadCompleted(Err(error)) => {
             model.tags = Status::Failed;
-            logger::errors(errors);
+            logger::error(error);
         },
         Msg::FeedMsg(feed_msg) => match &mut model.feed {
             Status::Loaded(feed_model) => article::feed::update(
                 feed_msg,
                 feed_model,
+                store,
                 &mut orders.proxy(Msg::FeedMsg),
             ),
             _ => {
@@ -205,18 +243,31 @@ pub fn update(
     }
 }
 
+/// Load the selected feed page and push it to the URL.
+fn change_feed(model: &mut Model, orders: &mut impl Orders<Msg, GMsg>) {
+    model.feed_request_id = RequestId::new();
+    fetch_feed(
+        model.session.viewer().cloned(),
+        &model.selected_feed,
+        model.feed_page,
+        model.feed_request_id,
+        orders,
+    );
+    route::push(Route::Home(model.query()));
+}
+
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view<'a>(model: &Model) -> ViewPage<'a, Msg> {
-    ViewPage::new("Conduit", view_content(model))
+pub fn view<'a>(model: &Model, store: &Store) -> ViewPage<'a, Msg> {
+    ViewPage::new("Conduit", view_content(model, store))
 }
 
 // ====== PRIVATE ======
 
-fn view_content(model: &Model) -> Node<Msg> {
-    div![class!["home-page"], view_banner(), view_feed(model),]
+fn view_content(model: &Model, store: &Store) -> Node<Msg> {
+    div![class!["home-page"], view_banner(), view_feed(model, store),]
 }
 
 fn view_banner() -> Node<Msg> {
@@ -225,18 +276,18 @@ fn view_banner() -> Node<Msg> {
         div![
             class!["container"],
             h1![class!["logo-font"], "conduit"],
-            p!["A place to share your knowledge."]
+            p![i18n::t("home.tagline")]
         ]
     ]
 }
 
 // ------ view feed ------
 
-fn view_feed(model: &Model) -> Node<Msg> {
+fn view_feed(model: &Model, store: &Store) -> Node<Msg> {
     match &model.feed {
         Status::Loading => empty![],
         Status::LoadingSlowly => loading::view_icon(),
-        Status::Failed => loading::view_error("feed"),
+        Status::Failed => loading::view_error(i18n::t("loading.feed_failed")),
         Status::Loaded(feed_model) => div![
             class!["container", "page"],
             div![
@@ -246,7 +297,7 @@ fn view_feed(model: &Model) -> Node<Msg> {
                     div![
                         class!["feed-toggle"],
                         view_tabs(model),
-                        article::feed::view_articles(feed_model)
+                        article::feed::view_articles(feed_model, store)
                             .els()
                             .map_msg(Msg::FeedMsg),
                         article::feed::view_pagination(
@@ -270,11 +321,16 @@ fn view_tabs(model: &Model) -> Node<Msg> {
     // -- Tabs --
 
     let your_feed = |viewer: Viewer| {
-        Tab::new("Your Feed", Msg::TabClicked(SelectedFeed::Your(viewer)))
+        Tab::new(
+            i18n::t("home.your_feed"),
+            Msg::TabClicked(SelectedFeed::Your(viewer)),
+        )
     };
 
-    let global_feed =
-        Tab::new("Global Feed", Msg::TabClicked(SelectedFeed::Global));
+    let global_feed = Tab::new(
+        i18n::t("home.global_feed"),
+        Msg::TabClicked(SelectedFeed::Global),
+    );
 
     let tag_feed = |tag: Tag| {
         Tab::new(format!("#{}", tag), Msg::TabClicked(SelectedFeed::Tag(tag)))
@@ -310,10 +366,10 @@ fn view_tags(model: &Model) -> Node<Msg> {
     match &model.tags {
         Status::Loading => empty![],
         Status::LoadingSlowly => loading::view_icon(),
-        Status::Failed => loading::view_error("tags"),
+        Status::Failed => loading::view_error(i18n::t("loading.tags_failed")),
         Status::Loaded(tags) => div![
             class!["sidebar"],
-            p!["Popular Tags"],
+            p![i18n::t("home.popular_tags")],
             div![class!["tag-list"], tags.clone().into_iter().map(view_tag)]
         ],
     }
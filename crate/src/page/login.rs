@@ -2,9 +2,9 @@ use super::ViewPage;
 use crate::{
     entity::{
         form::login::{Field, Form, Problem},
-        Viewer,
+        ApiError, Viewer,
     },
-    request,
+    i18n, logger, request,
     route::{self, Route},
     GMsg, Session,
 };
@@ -17,6 +17,7 @@ use seed::prelude::*;
 #[derive(Default)]
 pub struct Model {
     session: Session,
+    redirect: Option<Route<'static>>,
     problems: Vec<Problem>,
     form: Form,
 }
@@ -37,9 +38,10 @@ impl From<Model> for Session {
 //     Init
 // ------ ------
 
-pub fn init(session: Session) -> Model {
+pub fn init(session: Session, redirect: Option<Route<'static>>) -> Model {
     Model {
         session,
+        redirect,
         ..Model::default()
     }
 }
@@ -56,7 +58,13 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            if let Session::LoggedIn(_) = model.session {
+                let redirect = model
+                    .redirect
+                    .take()
+                    .unwrap_or(Route::Home(Default::default()));
+                route::redirect_to(redirect, orders);
+            }
         },
         _ => (),
     }
@@ -69,7 +77,7 @@ pub fn sink(
 pub enum Msg {
     FormSubmitted,
     FieldChanged(Field),
-    LoginCompleted(Result<Viewer, Vec<Problem>>),
+    LoginCompleted(Result<Viewer, ApiError>),
 }
 
 pub fn update(
@@ -94,11 +102,13 @@ pub fn update(
             model.form.upsert_field(field);
         },
         Msg::LoginCompleted(Ok(viewer)) => {
-            viewer.store();
+            if let Err(error) = viewer.store() {
+                logger::error(error);
+            }
             orders.send_g_msg(GMsg::SessionChanged(Session::LoggedIn(viewer)));
         },
-        Msg::LoginCompleted(Err(problems)) => {
-            model.problems = problems;
+        Msg::LoginCompleted(Err(error)) => {
+            model.problems = error.into_problems();
         },
     }
 }
@@ -108,7 +118,7 @@ pub fn update(
 // ------ ------
 
 pub fn view<'a>(model: &Model) -> ViewPage<'a, Msg> {
-    ViewPage::new("Login", view_content(model))
+    ViewPage::new(i18n::t("login.title"), view_content(model))
 }
 
 // ====== PRIVATE ======
@@ -122,12 +132,12 @@ fn view_content(model: &Model) -> Node<Msg> {
                 class!["row"],
                 div![
                     class!["col-md-6", "offset-md-3", "col-x32-12"],
-                    h1![class!["text-xs-center"], "Sign in"],
+                    h1![class!["text-xs-center"], i18n::t("auth.sign_in")],
                     p![
                         class!["text-xs-center"],
                         a![
                             attrs! {At::Href => Route::Register.to_string()},
-                            "Need an account?"
+                            i18n::t("login.need_account")
                         ]
                     ],
                     ul![
@@ -155,7 +165,7 @@ fn view_form(form: &Form) -> Node<Msg> {
         form.iter_fields().map(view_fieldset),
         button![
             class!["btn", "btn-lg", "btn-primary", "pull-xs-right"],
-            "Sign in"
+            i18n::t("auth.sign_in")
         ]
     ]
 }
@@ -168,7 +178,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Email",
+                    At::Placeholder => i18n::t("form.email"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -182,7 +192,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "password",
-                    At::Placeholder => "Password",
+                    At::Placeholder => i18n::t("form.password"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
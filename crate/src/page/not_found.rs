@@ -1,5 +1,5 @@
 use super::ViewPage;
-use crate::entity::Image;
+use crate::{entity::Image, i18n};
 use seed::prelude::*;
 
 // ------ ------
@@ -7,7 +7,7 @@ use seed::prelude::*;
 // ------ ------
 
 pub fn view<'a, Ms>() -> ViewPage<'a, Ms> {
-    ViewPage::new("Page Not Found", view_content())
+    ViewPage::new(i18n::t("not_found.title"), view_content())
 }
 
 // ====== PRIVATE ======
@@ -17,7 +17,7 @@ fn view_content<Ms>() -> Node<Ms> {
         id!("content"),
         class!["container"],
         attrs! {At::TabIndex => -1},
-        h1!["Not Found"],
+        h1![i18n::t("not_found.heading")],
         div![class!["row"], img![attrs! {At::Src => Image::error().url()}]]
     ]
 }
@@ -7,31 +7,35 @@ use crate::{
     entity::{
         article::{self, Article},
         author::{self, Author},
-        ErrorMessage, PageNumber, PaginatedList, Username, Viewer,
+        ApiError, ErrorMessage, PageNumber, PaginatedList, Username, Viewer,
     },
     helper::take,
-    loading, logger, page, request,
-    route::{self, Route},
+    i18n, loading, logger,
+    outbox::Mutation,
+    page,
+    request::{self, RequestId},
+    route::{self, ProfileQuery, ProfileTab, Route},
+    store::Store,
+    toast::Toast,
     GMsg, Session,
 };
 
-static DEFAULT_TITLE_PREFIX: &str = "Profile";
-static TITLE_PREFIX_FOR_ME: &str = "My Profile";
-
-async fn fetch_feed(
+fn fetch_feed(
     viewer: Option<Viewer>,
     username: Username<'static>,
     selected_feed: SelectedFeed,
     page_number: PageNumber,
-) -> Result<Msg, Msg> {
+    request_id: RequestId,
+    orders: &mut impl Orders<Msg, GMsg>,
+) {
     request::feed::load_for_profile(
+        orders,
         viewer,
         username,
         selected_feed,
         page_number,
-        Msg::FeedLoadCompleted,
-    )
-    .await
+        move |result| Msg::FeedLoadCompleted(request_id, result),
+    );
 }
 
 // ------ ------
@@ -46,7 +50,9 @@ pub struct Model<'a> {
     errors: Vec<ErrorMessage>,
     selected_feed: SelectedFeed,
     feed_page: PageNumber,
-    author: Status<'a, Author>,
+    author_request_id: RequestId,
+    feed_request_id: RequestId,
+    author: Status<'a, Username<'a>>,
     feed: Status<'a, article::feed::Model>,
 }
 
@@ -54,6 +60,16 @@ impl<'a> Model<'a> {
     pub const fn session(&self) -> &Session {
         &self.session
     }
+
+    fn query(&self) -> ProfileQuery {
+        ProfileQuery {
+            tab: match self.selected_feed {
+                SelectedFeed::MyArticles => ProfileTab::Articles,
+                SelectedFeed::FavoritedArticles => ProfileTab::Favorites,
+            },
+            page: self.feed_page,
+        }
+    }
 }
 
 impl<'a> From<Model<'a>> for Session {
@@ -77,13 +93,13 @@ impl<'a, T> Default for Status<'a, T> {
     }
 }
 
-impl<'a> Status<'a, Author> {
+impl<'a> Status<'a, Username<'a>> {
     pub fn username(&'a self) -> &Username<'a> {
         match self {
             Status::Loading(username)
             | Status::LoadingSlowly(username)
-            | Status::Failed(username) => username,
-            Status::Loaded(author) => author.username(),
+            | Status::Failed(username)
+            | Status::Loaded(username) => username,
         }
     }
 }
@@ -109,24 +125,40 @@ impl Default for SelectedFeed {
 pub fn init<'a>(
     session: Session,
     username: Username<'static>,
+    query: ProfileQuery,
     orders: &mut impl Orders<Msg, GMsg>,
 ) -> Model<'a> {
-    orders
-        .perform_cmd(loading::notify_on_slow_load(Msg::SlowLoadThresholdPassed))
-        .perform_cmd(request::author::load(
-            session.viewer().cloned(),
-            username.clone(),
-            Msg::AuthorLoadCompleted,
-        ))
-        .perform_cmd(fetch_feed(
-            session.viewer().cloned(),
-            username.clone(),
-            SelectedFeed::default(),
-            PageNumber::default(),
-        ));
+    let selected_feed = match query.tab {
+        ProfileTab::Articles => SelectedFeed::MyArticles,
+        ProfileTab::Favorites => SelectedFeed::FavoritedArticles,
+    };
+    let author_request_id = RequestId::new();
+    let feed_request_id = RequestId::new();
+
+    orders.perform_cmd(loading::notify_on_slow_load(
+        Msg::SlowLoadThresholdPassed,
+    ));
+    request::author::load(
+        orders,
+        session.viewer().cloned(),
+        username.clone(),
+        move |result| Msg::AuthorLoadCompleted(author_request_id, result),
+    );
+    fetch_feed(
+        session.viewer().cloned(),
+        username.clone(),
+        selected_feed,
+        query.page,
+        feed_request_id,
+        orders,
+    );
 
     Model {
         session,
+        selected_feed,
+        feed_page: query.page,
+        author_request_id,
+        feed_request_id,
         author: Status::Loading(username.clone()),
         feed: Status::Loading(username),
         ..Model::default()
@@ -145,7 +177,7 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            route::go_to(Route::Home(Default::default()), orders);
         },
         _ => (),
     }
@@ -163,19 +195,25 @@ pub enum Msg {
     UnfollowClicked,
     TabClicked(SelectedFeed),
     FeedPageClicked(PageNumber),
-    FollowChangeCompleted(Result<Author, Vec<ErrorMessage>>),
-    AuthorLoadCompleted(Result<Author, (Username<'static>, Vec<ErrorMessage>)>),
+    /// The first author is the original - `following` is restored on error.
+    FollowChangeCompleted(Author, Result<Author, ApiError>),
+    AuthorLoadCompleted(
+        RequestId,
+        Result<Author, (Username<'static>, ApiError)>,
+    ),
     FeedLoadCompleted(
-        Result<PaginatedList<Article>, (Username<'static>, Vec<ErrorMessage>)>,
+        RequestId,
+        Result<PaginatedList<Article>, (Username<'static>, ApiError)>,
     ),
     FeedMsg(article::feed::Msg),
     SlowLoadThresholdPassed,
 }
 
-#[allow(clippy::match_same_arms)]
+#[allow(clippy::match_same_arms, clippy::too_many_lines)]
 pub fn update(
     msg: Msg,
     model: &mut Model,
+    store: &mut Store,
     orders: &mut impl Orders<Msg, GMsg>,
 ) {
     match msg {
@@ -183,73 +221,93 @@ pub fn update(
             model.errors.clear();
         },
         Msg::FollowClicked => {
-            orders
-                .perform_cmd(request::follow::follow(
+            let username = model.author.username();
+            if let Some(original) = store
+                .change_author(username, |author| author.set_following(true))
+            {
+                orders.perform_cmd(request::follow::follow(
                     model.session.viewer().cloned(),
-                    model.author.username(),
-                    Msg::FollowChangeCompleted,
-                ))
-                .skip();
+                    username,
+                    move |result| Msg::FollowChangeCompleted(original, result),
+                ));
+            }
         },
         Msg::UnfollowClicked => {
-            orders
-                .perform_cmd(request::follow::unfollow(
+            let username = model.author.username();
+            if let Some(original) = store
+                .change_author(username, |author| author.set_following(false))
+            {
+                orders.perform_cmd(request::follow::unfollow(
                     model.session.viewer().cloned(),
-                    model.author.username(),
-                    Msg::FollowChangeCompleted,
-                ))
-                .skip();
+                    username,
+                    move |result| Msg::FollowChangeCompleted(original, result),
+                ));
+            }
         },
         Msg::TabClicked(selected_feed) => {
             model.selected_feed = selected_feed;
             model.feed_page = PageNumber::default();
-            orders.perform_cmd(fetch_feed(
-                model.session.viewer().cloned(),
-                model.author.username().to_static(),
-                selected_feed,
-                model.feed_page,
-            ));
+            change_feed(model, orders);
         },
         Msg::FeedPageClicked(page_number) => {
             model.feed_page = page_number;
-            orders.perform_cmd(fetch_feed(
-                model.session.viewer().cloned(),
-                model.author.username().to_static(),
-                model.selected_feed,
-                model.feed_page,
-            ));
+            change_feed(model, orders);
             page::scroll_to_top();
         },
-        Msg::FollowChangeCompleted(Ok(author)) => {
-            model.author = Status::Loaded(author)
+        Msg::FollowChangeCompleted(_, Ok(author)) => {
+            store.insert_author(author);
+        },
+        // keep the change, it will be sent once the browser is online
+        Msg::FollowChangeCompleted(original, Err(ApiError::Network)) => {
+            orders.send_g_msg(GMsg::MutationQueued(
+                Mutation::new_follow_change(&original),
+            ));
+        },
+        Msg::FollowChangeCompleted(original, Err(error)) => {
+            store.restore_following(&original);
+            logger::error(&error);
+            orders.send_g_msg(GMsg::ToastRequested(Toast::new_error(
+                i18n::t("toast.follow_failed"),
+                error,
+            )));
         },
-        Msg::FollowChangeCompleted(Err(errors)) => {
-            logger::errors(&errors);
-            model.errors = errors;
+        Msg::AuthorLoadCompleted(request_id, _)
+            if request_id != model.author_request_id =>
+        {
+            orders.skip();
         },
-        Msg::AuthorLoadCompleted(Ok(author)) => {
-            model.author = Status::Loaded(author)
+        Msg::AuthorLoadCompleted(_, Ok(author)) => {
+            model.author = Status::Loaded(store.insert_author(author))
         },
-        Msg::AuthorLoadCompleted(Err((username, errors))) => {
+        Msg::AuthorLoadCompleted(_, Err((username, error))) => {
             model.author = Status::Failed(username);
-            logger::errors(&errors);
-            model.errors = errors;
+            logger::error(&error);
+            model.errors = error.into_error_messages();
         },
-        Msg::FeedLoadCompleted(Ok(paginated_list)) => {
+        Msg::FeedLoadCompleted(request_id, _)
+            if request_id != model.feed_request_id =>
+        {
+            orders.skip();
+        },
+        Msg::FeedLoadCompleted(_, Ok(paginated_list)) => {
             model.feed = Status::Loaded(article::feed::init(
                 model.session.clone(),
                 paginated_list,
+                store,
             ));
+            orders.send_g_msg(GMsg::PageLoaded);
         },
-        Msg::FeedLoadCompleted(Err((username, errors))) => {
+        Msg::FeedLoadCompleted(_, Err((username, error))) => {
             model.feed = Status::Failed(username);
-            logger::errors(&errors);
-            model.errors = errors;
+            logger::error(&error);
+            model.errors = error.into_error_messages();
+            orders.send_g_msg(GMsg::PageLoaded);
         },
         Msg::FeedMsg(feed_msg) => match &mut model.feed {
             Status::Loaded(feed_model) => article::feed::update(
                 feed_msg,
                 feed_model,
+                store,
                 &mut orders.proxy(Msg::FeedMsg),
             ),
             _ => {
@@ -264,34 +322,49 @@ pub fn update(
     }
 }
 
+/// Load the selected feed page and push it to the URL.
+fn change_feed(model: &mut Model, orders: &mut impl Orders<Msg, GMsg>) {
+    let username = model.author.username().to_static();
+    model.feed_request_id = RequestId::new();
+    fetch_feed(
+        model.session.viewer().cloned(),
+        username.clone(),
+        model.selected_feed,
+        model.feed_page,
+        model.feed_request_id,
+        orders,
+    );
+    route::push(Route::Profile(Cow::Owned(username), model.query()));
+}
+
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view<'a>(model: &'a Model) -> ViewPage<'a, Msg> {
-    ViewPage::new(title_prefix(model), view_content(model))
+pub fn view<'a>(model: &'a Model, store: &Store) -> ViewPage<'a, Msg> {
+    ViewPage::new(title_prefix(model, store), view_content(model, store))
 }
 
 // ====== PRIVATE ======
 
 // ------ title prefix ------
 
-fn title_prefix<'a>(model: &Model) -> Cow<'a, str> {
+fn title_prefix<'a>(model: &Model, store: &Store) -> Cow<'a, str> {
     match &model.author {
         Status::Loading(username)
         | Status::LoadingSlowly(username)
         | Status::Failed(username) => {
             title_prefix_for_me(model.session.viewer(), username).into()
         },
-        Status::Loaded(Author::IsViewer(..)) => TITLE_PREFIX_FOR_ME.into(),
-        Status::Loaded(author) => {
-            title_prefix_for_other(author.username()).into()
+        Status::Loaded(username) => match store.author(username) {
+            Some(Author::IsViewer(..)) => i18n::t("profile.my_title").into(),
+            _ => title_prefix_for_other(username).into(),
         },
     }
 }
 
 fn title_prefix_for_other(username: &Username) -> String {
-    format!("Profile - {}", username.as_str())
+    i18n::t_with("profile.title_for", &[("username", &username.as_str())])
 }
 
 fn title_prefix_for_me(
@@ -300,48 +373,57 @@ fn title_prefix_for_me(
 ) -> &'static str {
     if let Some(viewer) = viewer {
         if username == viewer.username() {
-            return TITLE_PREFIX_FOR_ME;
+            return i18n::t("profile.my_title");
         }
     }
-    DEFAULT_TITLE_PREFIX
+    i18n::t("profile.title")
 }
 
 // ------ view functions ------
 
-fn view_content(model: &Model) -> Node<Msg> {
+fn view_content(model: &Model, store: &Store) -> Node<Msg> {
     match &model.author {
         Status::Loading(_) => empty![],
         Status::LoadingSlowly(_) => loading::view_icon(),
-        Status::Failed(_) => loading::view_error("profile"),
-        Status::Loaded(author) => div![
-            class!["profile-page"],
-            page::view_errors(Msg::DismissErrorsClicked, &model.errors),
+        Status::Failed(_) => {
+            loading::view_error(i18n::t("loading.profile_failed"))
+        },
+        Status::Loaded(username) => store.author(username).map_or_else(
+            || loading::view_error(i18n::t("loading.profile_failed")),
+            |author| view_profile(author, model, store),
+        ),
+    }
+}
+
+fn view_profile(author: &Author, model: &Model, store: &Store) -> Node<Msg> {
+    div![
+        class!["profile-page"],
+        page::view_errors(Msg::DismissErrorsClicked, &model.errors),
+        div![
+            class!["user-info"],
             div![
-                class!["user-info"],
+                class!["container"],
                 div![
-                    class!["container"],
+                    class!["row"],
                     div![
-                        class!["row"],
-                        div![
-                            class!["col-xs-12", "col-md-10", "offset-md-1"],
-                            img![
-                                class!["user-img"],
-                                attrs! {At::Src => author.profile().avatar.src() }
-                            ],
-                            h4![author.username().to_string()],
-                            p![author
-                                .profile()
-                                .bio
-                                .as_ref()
-                                .unwrap_or(&String::new())],
-                            view_follow_button(author, model)
-                        ]
+                        class!["col-xs-12", "col-md-10", "offset-md-1"],
+                        img![
+                            class!["user-img"],
+                            attrs! {At::Src => author.profile().avatar.src() }
+                        ],
+                        h4![author.username().to_string()],
+                        p![author
+                            .profile()
+                            .bio
+                            .as_ref()
+                            .unwrap_or(&String::new())],
+                        view_follow_button(author, model)
                     ]
                 ]
-            ],
-            view_feed(model)
+            ]
         ],
-    }
+        view_feed(model, store)
+    ]
 }
 
 fn view_follow_button(author: &Author, model: &Model) -> Node<Msg> {
@@ -363,11 +445,13 @@ fn view_follow_button(author: &Author, model: &Model) -> Node<Msg> {
 
 // ------ view feed ------
 
-fn view_feed(model: &Model) -> Node<Msg> {
+fn view_feed(model: &Model, store: &Store) -> Node<Msg> {
     match &model.feed {
         Status::Loading(_) => empty![],
         Status::LoadingSlowly(_) => loading::view_icon(),
-        Status::Failed(_) => loading::view_error("feed"),
+        Status::Failed(_) => {
+            loading::view_error(i18n::t("loading.feed_failed"))
+        },
         Status::Loaded(feed_model) => div![
             class!["container"],
             div![
@@ -377,7 +461,7 @@ fn view_feed(model: &Model) -> Node<Msg> {
                     div![
                         class!["articles-toggle"],
                         view_tabs(model.selected_feed),
-                        article::feed::view_articles(feed_model)
+                        article::feed::view_articles(feed_model, store)
                             .els()
                             .map_msg(Msg::FeedMsg),
                         article::feed::view_pagination(
@@ -397,11 +481,13 @@ fn view_tabs(selected_feed: SelectedFeed) -> Node<Msg> {
 
     // -- Tabs --
 
-    let my_articles =
-        Tab::new("My Articles", Msg::TabClicked(SelectedFeed::MyArticles));
+    let my_articles = Tab::new(
+        i18n::t("profile.my_articles"),
+        Msg::TabClicked(SelectedFeed::MyArticles),
+    );
 
     let favorited_articles = Tab::new(
-        "Favorited Articles",
+        i18n::t("profile.favorited_articles"),
         Msg::TabClicked(SelectedFeed::FavoritedArticles),
     );
 
@@ -2,9 +2,9 @@ use super::ViewPage;
 use crate::{
     entity::{
         form::register::{Field, Form, Problem},
-        Viewer,
+        ApiError, Viewer,
     },
-    request,
+    i18n, logger, request,
     route::{self, Route},
     GMsg, Session,
 };
@@ -56,7 +56,7 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            route::go_to(Route::Home(Default::default()), orders);
         },
         _ => (),
     }
@@ -69,7 +69,7 @@ pub fn sink(
 pub enum Msg {
     FormSubmitted,
     FieldChanged(Field),
-    RegisterCompleted(Result<Viewer, Vec<Problem>>),
+    RegisterCompleted(Result<Viewer, ApiError>),
 }
 
 pub fn update(
@@ -94,11 +94,13 @@ pub fn update(
             model.form.upsert_field(field);
         },
         Msg::RegisterCompleted(Ok(viewer)) => {
-            viewer.store();
+            if let Err(error) = viewer.store() {
+                logger::error(error);
+            }
             orders.send_g_msg(GMsg::SessionChanged(Session::LoggedIn(viewer)));
         },
-        Msg::RegisterCompleted(Err(problems)) => {
-            model.problems = problems;
+        Msg::RegisterCompleted(Err(error)) => {
+            model.problems = error.into_problems();
         },
     }
 }
@@ -108,7 +110,7 @@ pub fn update(
 // ------ ------
 
 pub fn view<'a>(model: &Model) -> ViewPage<'a, Msg> {
-    ViewPage::new("Register", view_content(model))
+    ViewPage::new(i18n::t("register.title"), view_content(model))
 }
 
 // ====== PRIVATE ======
@@ -122,12 +124,12 @@ fn view_content(model: &Model) -> Node<Msg> {
                 class!["row"],
                 div![
                     class!["col-md-6", "offset-md-3", "col-x32-12"],
-                    h1![class!["text-xs-center"], "Sign up"],
+                    h1![class!["text-xs-center"], i18n::t("auth.sign_up")],
                     p![
                         class!["text-xs-center"],
                         a![
-                            attrs! {At::Href => Route::Login.to_string()},
-                            "Have an account?"
+                            attrs! {At::Href => Route::Login(None).to_string()},
+                            i18n::t("register.have_account")
                         ]
                     ],
                     ul![
@@ -155,7 +157,7 @@ fn view_form(form: &Form) -> Node<Msg> {
         form.iter_fields().map(view_fieldset),
         button![
             class!["btn", "btn-lg", "btn-primary", "pull-xs-right"],
-            "Sign up"
+            i18n::t("auth.sign_up")
         ]
     ]
 }
@@ -168,7 +170,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Your Name",
+                    At::Placeholder => i18n::t("form.username"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -182,7 +184,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Email",
+                    At::Placeholder => i18n::t("form.email"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -196,7 +198,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "password",
-                    At::Placeholder => "Password",
+                    At::Placeholder => i18n::t("form.password"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
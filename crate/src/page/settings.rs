@@ -4,9 +4,9 @@ use seed::prelude::*;
 use crate::{
     entity::{
         form::settings::{Field, Form, Problem},
-        Viewer,
+        ApiError, Viewer,
     },
-    loading, request,
+    i18n, loading, logger, request,
     route::{self, Route},
     GMsg, Session,
 };
@@ -80,7 +80,7 @@ pub fn sink(
     match g_msg {
         GMsg::SessionChanged(session) => {
             model.session = session;
-            route::go_to(Route::Home, orders);
+            route::go_to(Route::Home(Default::default()), orders);
         },
         _ => (),
     }
@@ -93,8 +93,8 @@ pub fn sink(
 pub enum Msg {
     FormSubmitted,
     FieldChanged(Field),
-    FormLoadCompleted(Result<Form, Vec<Problem>>),
-    SaveCompleted(Result<Viewer, Vec<Problem>>),
+    FormLoadCompleted(Result<Form, ApiError>),
+    SaveCompleted(Result<Viewer, ApiError>),
     SlowLoadThresholdPassed,
 }
 
@@ -129,16 +129,18 @@ pub fn update(
         Msg::FormLoadCompleted(Ok(form)) => {
             model.status = Status::Loaded(form);
         },
-        Msg::FormLoadCompleted(Err(problems)) => {
-            model.problems = problems;
+        Msg::FormLoadCompleted(Err(error)) => {
+            model.problems = error.into_problems();
             model.status = Status::Failed;
         },
         Msg::SaveCompleted(Ok(viewer)) => {
-            viewer.store();
+            if let Err(error) = viewer.store() {
+                logger::error(error);
+            }
             orders.send_g_msg(GMsg::SessionChanged(Session::LoggedIn(viewer)));
         },
-        Msg::SaveCompleted(Err(problems)) => {
-            model.problems = problems;
+        Msg::SaveCompleted(Err(error)) => {
+            model.problems = error.into_problems();
         },
         Msg::SlowLoadThresholdPassed => {
             if let Status::Loading = model.status {
@@ -153,7 +155,7 @@ pub fn update(
 // ------ ------
 
 pub fn view<'a>(model: &Model) -> ViewPage<'a, Msg> {
-    ViewPage::new("Settings", view_content(model))
+    ViewPage::new(i18n::t("nav.settings"), view_content(model))
 }
 
 // ====== PRIVATE ======
@@ -180,7 +182,7 @@ fn view_content(model: &Model) -> Node<Msg> {
                             view_form(model),
                         ]
                     } else {
-                        vec![div!["Sign in to view your settings."]]
+                        vec![div![i18n::t("settings.sign_in")]]
                     }
                 ]
             ]
@@ -202,7 +204,7 @@ fn view_form(model: &Model) -> Node<Msg> {
             form.iter_fields().map(view_fieldset),
             button![
                 class!["btn", "btn-lg", "btn-primary", "pull-xs-right"],
-                "Update Settings"
+                i18n::t("settings.update")
             ]
         ],
         Status::Failed => loading::view_error("page"),
@@ -217,7 +219,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "URL of profile picture",
+                    At::Placeholder => i18n::t("form.avatar"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -231,7 +233,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Your Name",
+                    At::Placeholder => i18n::t("form.username"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -245,7 +247,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Rows => 8,
-                    At::Placeholder => "Short bio about you",
+                    At::Placeholder => i18n::t("form.bio"),
                 },
                 value,
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(Field::Bio(
@@ -259,7 +261,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "text",
-                    At::Placeholder => "Email",
+                    At::Placeholder => i18n::t("form.email"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -273,7 +275,7 @@ fn view_fieldset(field: &Field) -> Node<Msg> {
                 class!["form-control", "form-control-lg"],
                 attrs! {
                     At::Type => "password",
-                    At::Placeholder => "Password",
+                    At::Placeholder => i18n::t("form.password"),
                     At::Value => value
                 },
                 input_ev(Ev::Input, |new_value| Msg::FieldChanged(
@@ -1,14 +1,26 @@
 use crate::{
     coder::decoder,
-    entity::{form::Problem, ErrorMessage, Viewer},
+    config,
+    entity::{ApiError, Viewer},
     logger,
 };
-use seed::fetch;
+use gloo_timers::future::TimeoutFuture;
+use seed::{fetch, prelude::Orders};
+use serde::de::DeserializeOwned;
 use serde_json;
-use std::fmt::Debug;
+use std::{
+    cell::{Cell, RefCell},
+    convert::identity,
+    fmt::Debug,
+    future::Future,
+    rc::Rc,
+    time::Duration,
+};
+use wasm_bindgen_futures::spawn_local;
 
 pub mod article;
 pub mod author;
+mod cache;
 pub mod comment;
 pub mod favorite;
 pub mod feed;
@@ -18,13 +30,28 @@ pub mod register;
 pub mod settings;
 pub mod tag;
 
-static BASE_API_URL: &str =
-    "https://martinkavik-realworld.builtwithdark.com/api";
-const TIMEOUT: u32 = 5000;
+thread_local! {
+    static UNAUTHORIZED_HANDLER: RefCell<Option<Box<dyn Fn()>>> =
+        const { RefCell::new(None) };
+    static LAST_REQUEST_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Register the function called whenever the API responds with 401.
+pub fn set_unauthorized_handler(handler: impl Fn() + 'static) {
+    UNAUTHORIZED_HANDLER.with(|unauthorized_handler| {
+        unauthorized_handler.replace(Some(Box::new(handler)))
+    });
+}
 
 pub fn new(path: &str, viewer: Option<&Viewer>) -> fetch::Request {
-    let mut request = fetch::Request::new(format!("{}/{}", BASE_API_URL, path))
-        .timeout(TIMEOUT);
+    let config = config::current();
+    let mut request =
+        fetch::Request::new(format!("{}/{}", config.base_api_url, path))
+            .timeout(config.timeout);
+
+    for (name, value) in &config.headers {
+        request = request.header(name, value);
+    }
 
     if let Some(viewer) = viewer {
         let auth_token = viewer.auth_token.as_str();
@@ -34,50 +61,473 @@ pub fn new(path: &str, viewer: Option<&Viewer>) -> fetch::Request {
     request
 }
 
-pub fn fail_reason_into_problems<T: Debug>(
-    fail_reason: fetch::FailReason<T>,
-) -> Vec<Problem> {
-    fail_reason_into_errors(fail_reason)
-        .into_iter()
-        .map(|error| Problem::new_server_error(error.into_inner()))
-        .collect()
+/// Create a GET request that is sent again when it fails with a timeout
+/// or a server error (5xx).
+pub fn get(path: &str, viewer: Option<&Viewer>) -> Get {
+    Get {
+        path: path.to_owned(),
+        viewer: viewer.cloned(),
+        cache_ttl: None,
+        max_retries: DEFAULT_MAX_RETRIES,
+        base_retry_delay: DEFAULT_BASE_RETRY_DELAY,
+    }
+}
+
+/// Remove cached responses whose path starts with `path_prefix`.
+/// Call it when a mutation changes data returned by cached GET requests.
+pub fn invalidate_cache(path_prefix: &str) {
+    cache::invalidate(path_prefix);
 }
 
-pub fn fail_reason_into_errors<T: Debug>(
+pub fn clear_cache() {
+    cache::clear();
+}
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_BASE_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+// ------ RequestId ------
+
+/// Unique id of a request. Pages remember the id of the latest request
+/// and ignore responses to the older ones.
+///
+/// The default id doesn't belong to any request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    pub fn new() -> Self {
+        LAST_REQUEST_ID.with(|last_request_id| {
+            let request_id = last_request_id.get() + 1;
+            last_request_id.set(request_id);
+            Self(request_id)
+        })
+    }
+}
+
+// ------ Get ------
+
+pub struct Get {
+    path: String,
+    viewer: Option<Viewer>,
+    cache_ttl: Option<Duration>,
+    max_retries: u32,
+    base_retry_delay: Duration,
+}
+
+impl Get {
+    /// Cache successful responses. See `perform_json_data`
+    /// for how cached data are passed to the callback.
+    pub const fn cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Send the failed request again at most `max_retries` times
+    /// (3 times by default).
+    pub const fn retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// The delay before the first retry (250 ms by default);
+    /// it doubles with each next retry.
+    pub const fn retry_delay(mut self, base_delay: Duration) -> Self {
+        self.base_retry_delay = base_delay;
+        self
+    }
+
+    /// Retries are hidden from the caller - `f` is called only once
+    /// with the last result, so the page stays in its loading state meanwhile.
+    /// Only cached data younger than the cache `ttl` are used.
+    pub async fn fetch_json_data<T, Ms>(
+        self,
+        f: impl FnOnce(fetch::ResponseDataResult<T>) -> Ms,
+    ) -> Result<Ms, Ms>
+    where
+        T: DeserializeOwned + 'static,
+        Ms: 'static,
+    {
+        match self.cached() {
+            Some(Cached {
+                data,
+                stale: false,
+            }) => Ok(f(Ok(data))),
+            _ => Ok(f(self.fetch().await)),
+        }
+    }
+
+    /// Like `fetch_json_data`, but cached data are passed to `f` immediately
+    /// and, when they are older than the cache `ttl`, the refreshed data
+    /// once they arrive. Messages are sent to the app through `orders`.
+    pub fn perform_json_data<T, Ms, GMs>(
+        self,
+        orders: &mut impl Orders<Ms, GMs>,
+        f: impl Fn(fetch::ResponseDataResult<T>) -> Ms + 'static,
+    ) where
+        T: DeserializeOwned + Debug + 'static,
+        Ms: 'static,
+        GMs: 'static,
+    {
+        let (app, msg_mapper) = (orders.clone_app(), orders.msg_mapper());
+        spawn_local(async move {
+            revalidate(
+                self.cached(),
+                || self.fetch(),
+                |data_result| app.update(msg_mapper(f(data_result))),
+            )
+            .await;
+        });
+    }
+
+    // ------ private ------
+
+    fn cache_key(&self) -> cache::Key {
+        cache::Key {
+            path: self.path.clone(),
+            username: self
+                .viewer
+                .as_ref()
+                .map(|viewer| viewer.username().as_str().to_owned()),
+        }
+    }
+
+    fn cached<T: DeserializeOwned>(&self) -> Option<Cached<T>> {
+        let cache_ttl = self.cache_ttl?;
+        let entry = cache::get(&self.cache_key())?;
+        serde_json::from_str(&entry.json).ok().map(|data| Cached {
+            data,
+            stale: entry.is_stale(cache_ttl),
+        })
+    }
+
+    async fn fetch<T>(&self) -> fetch::ResponseDataResult<T>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let mut retry = 0;
+        loop {
+            // A new request for each attempt, because an aborted request
+            // (e.g. on timeout) can't be sent again.
+            let fetch_object = match new(&self.path, self.viewer.as_ref())
+                .fetch_string(identity)
+                .await
+            {
+                Ok(fetch_object) | Err(fetch_object) => fetch_object,
+            };
+            let json = fetch_object
+                .result
+                .as_ref()
+                .ok()
+                .and_then(|response| response.data.as_ref().ok())
+                .cloned();
+
+            match decode_fetch_object(fetch_object).response_data() {
+                Err(ref fail_reason)
+                    if retry < self.max_retries
+                        && is_retryable(fail_reason) =>
+                {
+                    TimeoutFuture::new(backoff_delay(
+                        self.base_retry_delay,
+                        retry,
+                    ))
+                    .await;
+                    retry += 1;
+                },
+                Ok(data) => {
+                    if let (Some(_), Some(json)) = (self.cache_ttl, json) {
+                        cache::insert(self.cache_key(), json);
+                    }
+                    return Ok(data);
+                },
+                data_result => return data_result,
+            }
+        }
+    }
+}
+
+pub fn fail_reason_into_api_error<T: DeserializeOwned + Debug>(
     fail_reason: fetch::FailReason<T>,
-) -> Vec<ErrorMessage> {
+) -> ApiError {
     match fail_reason {
-        fetch::FailReason::RequestError(request_error, _) => {
-            logger::error(request_error);
-            vec!["Request error".into()]
+        fetch::FailReason::RequestError(
+            fetch::RequestError::DomException(exception),
+            _,
+        ) => {
+            // `seed` aborts requests on timeout.
+            if exception.name() == "AbortError" {
+                ApiError::Timeout
+            } else {
+                logger::error(exception);
+                ApiError::Network
+            }
         },
         fetch::FailReason::DataError(data_error, _) => {
-            logger::error(data_error);
-            vec!["Data error".into()]
+            decode_data_error::<T>(data_error)
         },
-        fetch::FailReason::Status(_, fetch_object) => {
-            // response isn't ok, but maybe contains error messages - try to decode them:
-            match fetch_object.result.unwrap().data {
-                Err(fetch::DataError::SerdeError(_, json)) => {
-                    decode_server_errors(&json).unwrap_or_else(|serde_error| {
-                        logger::error(serde_error);
-                        vec!["Data error".into()]
-                    })
-                },
-                data => {
-                    logger::error(data);
-                    vec!["Data error".into()]
-                },
-            }
+        fetch::FailReason::Status(status, fetch_object) => match status.code {
+            401 => {
+                UNAUTHORIZED_HANDLER.with(|unauthorized_handler| {
+                    if let Some(handler) = &*unauthorized_handler.borrow() {
+                        handler();
+                    }
+                });
+                ApiError::Unauthorized
+            },
+            403 => ApiError::Forbidden,
+            404 => ApiError::NotFound,
+            code => {
+                // response isn't ok, but maybe contains error messages - try to decode them:
+                let server_errors = fetch_object
+                    .result
+                    .ok()
+                    .and_then(|response| response.data.err())
+                    .and_then(|data_error| match data_error {
+                        fetch::DataError::SerdeError(_, json) => {
+                            decode_server_errors(&json).ok()
+                        },
+                        fetch::DataError::DomException(_) => None,
+                    });
+                match server_errors {
+                    Some(server_errors) if code < 500 => server_errors,
+                    _ => ApiError::Status(code),
+                }
+            },
         },
     }
 }
 
 // ====== PRIVATE ======
 
-fn decode_server_errors(
-    json: &str,
-) -> Result<Vec<ErrorMessage>, serde_json::Error> {
+struct Cached<T> {
+    data: T,
+    stale: bool,
+}
+
+/// Pass cached data to `deliver` and then the refreshed data,
+/// if the cached ones are missing or stale.
+/// A failed refresh of stale data is only logged - the page keeps them.
+async fn revalidate<T, Fut>(
+    cached: Option<Cached<T>>,
+    refresh: impl FnOnce() -> Fut,
+    mut deliver: impl FnMut(fetch::ResponseDataResult<T>),
+) where
+    T: Debug,
+    Fut: Future<Output = fetch::ResponseDataResult<T>>,
+{
+    match cached {
+        Some(Cached {
+            data,
+            stale: false,
+        }) => deliver(Ok(data)),
+        Some(Cached {
+            data,
+            stale: true,
+        }) => {
+            deliver(Ok(data));
+            match refresh().await {
+                Ok(data) => deliver(Ok(data)),
+                Err(fail_reason) => logger::error(fail_reason),
+            }
+        },
+        None => deliver(refresh().await),
+    }
+}
+
+fn is_retryable<T>(fail_reason: &fetch::FailReason<T>) -> bool {
+    match fail_reason {
+        fetch::FailReason::RequestError(
+            fetch::RequestError::DomException(exception),
+            _,
+        ) => exception.name() == "AbortError",
+        fetch::FailReason::Status(status, _) => status.code >= 500,
+        fetch::FailReason::DataError(..) => false,
+    }
+}
+
+/// Exponential backoff with jitter - the delay in milliseconds
+/// for the `retry` (0, 1, 2, ..) is a random number
+/// between `base * 2^retry / 2` and `base * 2^retry`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn backoff_delay(base_delay: Duration, retry: u32) -> u32 {
+    let max_delay =
+        base_delay.as_secs_f64() * 1000. * f64::from(2_u32.pow(retry));
+    (max_delay / 2. * (1. + js_sys::Math::random())) as u32
+}
+
+fn decode_server_errors(json: &str) -> Result<ApiError, serde_json::Error> {
     serde_json::from_str::<decoder::ErrorMessages>(json)
-        .map(decoder::ErrorMessages::into_error_messages)
+        .map(decoder::ErrorMessages::into_api_error)
+}
+
+/// Same as `seed`'s `Request::fetch_json`, but from an already fetched body.
+fn decode_fetch_object<T: DeserializeOwned>(
+    fetch_object: fetch::FetchObject<String>,
+) -> fetch::FetchObject<T> {
+    let fetch::FetchObject {
+        request,
+        result,
+    } = fetch_object;
+    fetch::FetchObject {
+        request,
+        result: result.map(|response| fetch::ResponseWithDataResult {
+            raw: response.raw,
+            status: response.status,
+            data: response.data.and_then(|json| {
+                serde_json::from_str(&json).map_err(|serde_error| {
+                    fetch::DataError::SerdeError(Rc::new(serde_error), json)
+                })
+            }),
+        }),
+    }
+}
+
+/// Decode the response body again to find out the path to the invalid value.
+fn decode_data_error<T: DeserializeOwned>(
+    data_error: fetch::DataError,
+) -> ApiError {
+    match data_error {
+        fetch::DataError::SerdeError(serde_error, json) => {
+            logger::error(serde_error);
+            let deserializer = &mut serde_json::Deserializer::from_str(&json);
+            match serde_path_to_error::deserialize::<_, T>(deserializer) {
+                Err(error) => ApiError::new_decode(
+                    error.path().to_string(),
+                    error.into_inner().to_string(),
+                ),
+                Ok(_) => ApiError::new_decode(".", "unknown error"),
+            }
+        },
+        fetch::DataError::DomException(exception) => {
+            logger::error(&exception);
+            ApiError::new_decode(".", exception.message())
+        },
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::rc::Rc;
+    use wasm_bindgen_test::*;
+
+    #[derive(Deserialize, Debug)]
+    struct RootDecoder {
+        #[allow(dead_code)]
+        article: ArticleDecoder,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct ArticleDecoder {
+        #[allow(dead_code)]
+        favorites_count: usize,
+    }
+
+    #[wasm_bindgen_test]
+    fn request_id_test() {
+        // ====== ACT ======
+        let first_id = RequestId::new();
+        let second_id = RequestId::new();
+
+        // ====== ASSERT ======
+        assert_ne!(first_id, second_id);
+        assert_ne!(first_id, RequestId::default());
+    }
+
+    #[wasm_bindgen_test]
+    fn backoff_delay_test() {
+        for retry in 0..DEFAULT_MAX_RETRIES {
+            // ====== ACT ======
+            let delay = backoff_delay(DEFAULT_BASE_RETRY_DELAY, retry);
+
+            // ====== ASSERT ======
+            let max_delay = 250 * 2_u32.pow(retry);
+            assert!(delay >= max_delay / 2 && delay <= max_delay);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn revalidate_stale_test() {
+        // ====== ARRANGE ======
+        let cached = Cached {
+            data: 1,
+            stale: true,
+        };
+        let mut deliveries = Vec::new();
+
+        // ====== ACT ======
+        revalidate(
+            Some(cached),
+            || async { Ok(2) },
+            |data_result| deliveries.push(data_result.ok()),
+        )
+        .await;
+
+        // ====== ASSERT ======
+        assert_eq!(deliveries, vec![Some(1), Some(2)]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn revalidate_fresh_test() {
+        // ====== ARRANGE ======
+        let cached = Cached {
+            data: 1,
+            stale: false,
+        };
+        let mut deliveries = Vec::new();
+
+        // ====== ACT ======
+        revalidate(
+            Some(cached),
+            || async { Ok(2) },
+            |data_result| deliveries.push(data_result.ok()),
+        )
+        .await;
+
+        // ====== ASSERT ======
+        assert_eq!(deliveries, vec![Some(1)]);
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_data_error_test() {
+        // ====== ARRANGE ======
+        let json = r#"{ "article": { "favoritesCount": "many" } }"#;
+        let serde_error =
+            serde_json::from_str::<RootDecoder>(json).unwrap_err();
+        let data_error =
+            fetch::DataError::SerdeError(Rc::new(serde_error), json.into());
+
+        // ====== ACT ======
+        let api_error = decode_data_error::<RootDecoder>(data_error);
+
+        // ====== ASSERT ======
+        match api_error {
+            ApiError::Decode {
+                path,
+                ..
+            } => assert_eq!(path, "article.favoritesCount"),
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_server_errors_test() {
+        // ====== ARRANGE ======
+        let json = r#"{ "errors": { "email": ["is invalid"] } }"#;
+
+        // ====== ACT ======
+        let api_error = decode_server_errors(json).unwrap();
+
+        // ====== ASSERT ======
+        match api_error {
+            ApiError::Validation(errors) => {
+                assert_eq!(errors["email"], vec!["is invalid".to_owned()]);
+            },
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
 }
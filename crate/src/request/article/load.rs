@@ -1,34 +1,46 @@
-use std::{borrow::Cow, future::Future};
+use std::{borrow::Cow, time::Duration};
 
-use seed::fetch::ResponseDataResult;
+use seed::{fetch::ResponseDataResult, prelude::Orders};
 use serde::Deserialize;
 
 use crate::{
     coder::decoder,
-    entity::{Article, ErrorMessage, Slug, Viewer},
+    entity::{ApiError, Article, Slug, Viewer},
     request,
 };
 
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct RootDecoder {
     article: decoder::Article,
 }
 
-pub fn load<Ms: 'static>(
+pub fn load<Ms: 'static, GMs: 'static>(
+    orders: &mut impl Orders<Ms, GMs>,
     viewer: Option<Viewer>,
     slug: &Slug,
-    f: fn(Result<Article, Vec<ErrorMessage>>) -> Ms,
-) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new(&format!("articles/{}", slug.as_str()), viewer.as_ref())
-        .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result.map_err(request::fail_reason_into_errors).and_then(
-                move |root_decoder| {
-                    root_decoder
-                        .article
-                        .try_into_article(viewer.map(Cow::Owned))
-                        .map_err(|error| vec![error])
-                },
-            ))
-        })
+    f: impl Fn(Result<Article, ApiError>) -> Ms + 'static,
+) {
+    request::get(&format!("articles/{}", slug.as_str()), viewer.as_ref())
+        .cache(CACHE_TTL)
+        .perform_json_data(
+            orders,
+            move |data_result: ResponseDataResult<RootDecoder>| {
+                f(data_result
+                    .map_err(request::fail_reason_into_api_error)
+                    .and_then(|root_decoder| {
+                        root_decoder
+                            .article
+                            .try_into_article(viewer.clone().map(Cow::Owned))
+                            .map_err(|error| {
+                                ApiError::new_decode(
+                                    "article",
+                                    error.into_inner(),
+                                )
+                            })
+                    }))
+            },
+        );
 }
@@ -5,7 +5,7 @@ use serde::Deserialize;
 
 use crate::{
     coder::decoder,
-    entity::{form::article_editor::Problem, Article, Slug, Viewer},
+    entity::{ApiError, Article, Slug, Viewer},
     request,
 };
 
@@ -19,20 +19,20 @@ struct RootDecoder {
 pub fn load_for_editor<Ms: 'static>(
     viewer: Option<Viewer>,
     slug: Slug,
-    f: fn(Result<Article, (Slug, Vec<Problem>)>) -> Ms,
+    f: impl FnOnce(Result<Article, (Slug, ApiError)>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new(&format!("articles/{}", slug.as_str()), viewer.as_ref())
+    request::get(&format!("articles/{}", slug.as_str()), viewer.as_ref())
         .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
             f(data_result
-                .map_err(request::fail_reason_into_problems)
+                .map_err(request::fail_reason_into_api_error)
                 .and_then(move |root_decoder| {
                     root_decoder
                         .article
                         .try_into_article(viewer.map(Cow::Owned))
                         .map_err(|error| {
-                            vec![Problem::new_server_error(error.into_inner())]
+                            ApiError::new_decode("article", error.into_inner())
                         })
                 })
-                .map_err(|problems| (slug, problems)))
+                .map_err(|error| (slug, error)))
         })
 }
@@ -6,8 +6,7 @@ use serde::Deserialize;
 use crate::{
     coder::decoder,
     entity::{
-        form::article_editor::{Problem, ValidForm},
-        Article, Slug, Viewer,
+        form::article_editor::ValidForm, ApiError, Article, Slug, Viewer,
     },
     request,
 };
@@ -22,21 +21,25 @@ pub fn update<Ms: 'static>(
     viewer: Option<Viewer>,
     valid_form: &ValidForm,
     slug: &Slug,
-    f: fn(Result<Article, Vec<Problem>>) -> Ms,
+    f: impl FnOnce(Result<Article, ApiError>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
     request::new(&format!("articles/{}", slug.as_str()), viewer.as_ref())
         .method(Method::Put)
         .send_json(&valid_form.to_encoder())
         .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result.map_err(request::fail_reason_into_problems).and_then(
-                move |root_decoder| {
+            if data_result.is_ok() {
+                request::invalidate_cache("articles");
+                request::invalidate_cache("tags");
+            }
+            f(data_result
+                .map_err(request::fail_reason_into_api_error)
+                .and_then(move |root_decoder| {
                     root_decoder
                         .article
                         .try_into_article(viewer.map(Cow::Owned))
                         .map_err(|error| {
-                            vec![Problem::new_server_error(error.into_inner())]
+                            ApiError::new_decode("article", error.into_inner())
                         })
-                },
-            ))
+                }))
         })
 }
@@ -1,11 +1,13 @@
 use crate::{
     coder::decoder,
-    entity::{Author, ErrorMessage, Username, Viewer},
+    entity::{ApiError, Author, Username, Viewer},
     request,
 };
-use seed::fetch::ResponseDataResult;
+use seed::{fetch::ResponseDataResult, prelude::Orders};
 use serde::Deserialize;
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -14,19 +16,25 @@ struct RootDecoder {
 }
 
 #[allow(clippy::type_complexity)]
-pub async fn load<Ms: 'static>(
+pub fn load<Ms: 'static, GMs: 'static>(
+    orders: &mut impl Orders<Ms, GMs>,
     viewer: Option<Viewer>,
     username: Username<'static>,
-    f: fn(Result<Author, (Username<'static>, Vec<ErrorMessage>)>) -> Ms,
-) -> Result<Ms, Ms> {
-    request::new(&format!("profiles/{}", username.as_str()), viewer.as_ref())
-        .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result
-                .map(move |root_decoder| {
-                    root_decoder.profile.into_author(viewer.map(Cow::Owned))
-                })
-                .map_err(request::fail_reason_into_errors)
-                .map_err(move |errors| (username, errors)))
-        })
-        .await
+    f: impl Fn(Result<Author, (Username<'static>, ApiError)>) -> Ms + 'static,
+) {
+    request::get(&format!("profiles/{}", username.as_str()), viewer.as_ref())
+        .cache(CACHE_TTL)
+        .perform_json_data(
+            orders,
+            move |data_result: ResponseDataResult<RootDecoder>| {
+                f(data_result
+                    .map(|root_decoder| {
+                        root_decoder
+                            .profile
+                            .into_author(viewer.clone().map(Cow::Owned))
+                    })
+                    .map_err(request::fail_reason_into_api_error)
+                    .map_err(|error| (username.clone(), error)))
+            },
+        );
 }
@@ -0,0 +1,113 @@
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, time::Duration};
+
+/// The oldest entries are removed when the cache grows over this limit.
+const MAX_ENTRIES: usize = 100;
+
+thread_local! {
+    static ENTRIES: RefCell<HashMap<Key, Entry>> = RefCell::new(HashMap::new());
+}
+
+// ------ Key ------
+
+/// Responses differ for each viewer (e.g. `favorited` or `following`),
+/// so the viewer is a part of the key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub path: String,
+    pub username: Option<String>,
+}
+
+// ------ Entry ------
+
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub json: String,
+    stored_at: f64,
+}
+
+impl Entry {
+    pub fn is_stale(&self, ttl: Duration) -> bool {
+        now() - self.stored_at > ttl.as_secs_f64() * 1000.
+    }
+}
+
+// ------ public functions ------
+
+pub fn get(key: &Key) -> Option<Entry> {
+    ENTRIES.with(|entries| entries.borrow().get(key).cloned())
+}
+
+pub fn insert(key: Key, json: String) {
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest_key = entries
+                .iter()
+                .min_by(|(_, a), (_, b)| {
+                    a.stored_at
+                        .partial_cmp(&b.stored_at)
+                        .unwrap_or(Ordering::Equal)
+                })
+                .map(|(key, _)| key.clone());
+            if let Some(oldest_key) = oldest_key {
+                entries.remove(&oldest_key);
+            }
+        }
+        entries.insert(
+            key,
+            Entry {
+                json,
+                stored_at: now(),
+            },
+        );
+    });
+}
+
+/// Remove entries for all viewers whose path starts with `path_prefix`.
+pub fn invalidate(path_prefix: &str) {
+    ENTRIES.with(|entries| {
+        entries.borrow_mut().retain(|key, _| !key.path.starts_with(path_prefix));
+    });
+}
+
+pub fn clear() {
+    ENTRIES.with(|entries| entries.borrow_mut().clear());
+}
+
+// ====== PRIVATE ======
+
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn key(path: &str) -> Key {
+        Key {
+            path: path.to_owned(),
+            username: None,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn invalidate_test() {
+        // ====== ARRANGE ======
+        clear();
+        insert(key("articles/my-slug"), "{}".into());
+        insert(key("articles?limit=10&offset=0"), "{}".into());
+        insert(key("tags"), "{}".into());
+
+        // ====== ACT ======
+        invalidate("articles");
+
+        // ====== ASSERT ======
+        assert!(get(&key("articles/my-slug")).is_none());
+        assert!(get(&key("articles?limit=10&offset=0")).is_none());
+        assert!(get(&key("tags")).is_some());
+    }
+}
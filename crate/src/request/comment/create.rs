@@ -5,7 +5,7 @@ use serde::Deserialize;
 
 use crate::{
     coder::{decoder, encoder},
-    entity::{Comment, ErrorMessage, Slug, Viewer},
+    entity::{ApiError, Comment, Slug, Viewer},
     request,
 };
 
@@ -19,7 +19,7 @@ pub fn create<Ms: 'static>(
     viewer: Option<Viewer>,
     slug: &Slug,
     text: String,
-    f: fn(Result<Comment, Vec<ErrorMessage>>) -> Ms,
+    f: impl FnOnce(Result<Comment, ApiError>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
     request::new(
         &format!("articles/{}/comments", slug.as_str()),
@@ -29,14 +29,16 @@ pub fn create<Ms: 'static>(
     .send_json(&encoder::Comment::new(text))
     .fetch_json_data(
         move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result.map_err(request::fail_reason_into_errors).and_then(
-                move |root_decoder| {
+            f(data_result
+                .map_err(request::fail_reason_into_api_error)
+                .and_then(move |root_decoder| {
                     root_decoder
                         .comment
                         .try_into_comment(viewer.map(Cow::Owned))
-                        .map_err(|error| vec![error])
-                },
-            ))
+                        .map_err(|error| {
+                            ApiError::new_decode("comment", error.into_inner())
+                        })
+                }))
         },
     )
 }
@@ -4,7 +4,7 @@ use indexmap::IndexMap;
 use seed::fetch::{Method, ResponseDataResult};
 
 use crate::{
-    entity::{CommentId, ErrorMessage, Slug, Viewer},
+    entity::{ApiError, CommentId, Slug, Viewer},
     request,
 };
 
@@ -14,7 +14,7 @@ pub fn delete<Ms: 'static>(
     viewer: Option<&Viewer>,
     slug: &Slug,
     comment_id: CommentId,
-    f: fn(Result<CommentId, Vec<ErrorMessage>>) -> Ms,
+    f: fn(Result<CommentId, ApiError>) -> Ms,
 ) -> impl Future<Output = Result<Ms, Ms>> {
     request::new(
         &format!("articles/{}/comments/{}", slug.as_str(), comment_id.as_str()),
@@ -25,7 +25,7 @@ pub fn delete<Ms: 'static>(
         move |data_result: ResponseDataResult<RootDecoder>| {
             f(data_result
                 .map(move |_| comment_id)
-                .map_err(request::fail_reason_into_errors))
+                .map_err(request::fail_reason_into_api_error))
         },
     )
 }
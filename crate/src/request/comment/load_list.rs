@@ -5,7 +5,7 @@ use serde::Deserialize;
 
 use crate::{
     coder::decoder,
-    entity::{Comment, ErrorMessage, Slug, Viewer},
+    entity::{ApiError, Comment, Slug, Viewer},
     logger, request,
 };
 
@@ -37,9 +37,9 @@ impl RootDecoder {
 pub fn load_list<Ms: 'static>(
     viewer: Option<Viewer>,
     slug: &Slug,
-    f: fn(Result<VecDeque<Comment>, Vec<ErrorMessage>>) -> Ms,
+    f: impl FnOnce(Result<VecDeque<Comment>, ApiError>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new(
+    request::get(
         &format!("articles/{}/comments", slug.as_str()),
         viewer.as_ref(),
     )
@@ -49,7 +49,7 @@ pub fn load_list<Ms: 'static>(
                 .map(move |root_decoder| {
                     root_decoder.into_comments(viewer.as_ref())
                 })
-                .map_err(request::fail_reason_into_errors))
+                .map_err(request::fail_reason_into_api_error))
         },
     )
 }
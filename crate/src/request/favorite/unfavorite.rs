@@ -1,6 +1,6 @@
 use crate::{
     coder::decoder,
-    entity::{Article, ErrorMessage, Slug, Viewer},
+    entity::{ApiError, Article, Slug, Viewer},
     request,
 };
 use seed::fetch::{Method, ResponseDataResult};
@@ -16,7 +16,7 @@ struct RootDecoder {
 pub fn unfavorite<Ms: 'static>(
     viewer: Option<Viewer>,
     slug: &Slug,
-    f: fn(Result<Article, Vec<ErrorMessage>>) -> Ms,
+    f: impl FnOnce(Result<Article, ApiError>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
     request::new(
         &format!("articles/{}/favorite", slug.as_str()),
@@ -25,14 +25,19 @@ pub fn unfavorite<Ms: 'static>(
     .method(Method::Delete)
     .fetch_json_data(
         move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result.map_err(request::fail_reason_into_errors).and_then(
-                move |root_decoder| {
+            if data_result.is_ok() {
+                request::invalidate_cache("articles");
+            }
+            f(data_result
+                .map_err(request::fail_reason_into_api_error)
+                .and_then(move |root_decoder| {
                     root_decoder
                         .article
                         .try_into_article(viewer.map(Cow::Owned))
-                        .map_err(|error| vec![error])
-                },
-            ))
+                        .map_err(|error| {
+                            ApiError::new_decode("article", error.into_inner())
+                        })
+                }))
         },
     )
 }
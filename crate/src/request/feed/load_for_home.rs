@@ -1,19 +1,22 @@
 use crate::{
     coder::decoder,
-    entity::{Article, ErrorMessage, PageNumber, PaginatedList, Viewer},
+    entity::{ApiError, Article, PageNumber, PaginatedList, Viewer},
     logger,
     page::home::SelectedFeed,
     request,
 };
 use lazy_static::lazy_static;
-use seed::fetch::ResponseDataResult;
+use seed::{fetch::ResponseDataResult, prelude::Orders};
 use serde::Deserialize;
-use std::{borrow::Cow, future::Future, num::NonZeroUsize};
+use std::{borrow::Cow, num::NonZeroUsize, time::Duration};
 
 lazy_static! {
     static ref ARTICLES_PER_PAGE: NonZeroUsize = NonZeroUsize::new(10).unwrap();
 }
 
+/// Feeds change often - new articles and favorites.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct RootDecoder {
@@ -68,18 +71,23 @@ pub fn request_url(
     format!("articles{}?{}", path.unwrap_or_default(), parameters.join("&"))
 }
 
-pub fn load_for_home<Ms: 'static>(
+pub fn load_for_home<Ms: 'static, GMs: 'static>(
+    orders: &mut impl Orders<Ms, GMs>,
     viewer: Option<Viewer>,
     selected_feed: &SelectedFeed,
     page_number: PageNumber,
-    f: fn(Result<PaginatedList<Article>, Vec<ErrorMessage>>) -> Ms,
-) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new(&request_url(selected_feed, page_number), viewer.as_ref())
-        .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result
-                .map(move |root_decoder| {
-                    root_decoder.into_paginated_list(&viewer)
-                })
-                .map_err(request::fail_reason_into_errors))
-        })
+    f: impl Fn(Result<PaginatedList<Article>, ApiError>) -> Ms + 'static,
+) {
+    request::get(&request_url(selected_feed, page_number), viewer.as_ref())
+        .cache(CACHE_TTL)
+        .perform_json_data(
+            orders,
+            move |data_result: ResponseDataResult<RootDecoder>| {
+                f(data_result
+                    .map(|root_decoder| {
+                        root_decoder.into_paginated_list(&viewer)
+                    })
+                    .map_err(request::fail_reason_into_api_error))
+            },
+        );
 }
@@ -1,21 +1,22 @@
 use crate::{
     coder::decoder,
-    entity::{
-        Article, ErrorMessage, PageNumber, PaginatedList, Username, Viewer,
-    },
+    entity::{ApiError, Article, PageNumber, PaginatedList, Username, Viewer},
     logger,
     page::profile::SelectedFeed,
     request,
 };
 use lazy_static::lazy_static;
-use seed::fetch::ResponseDataResult;
+use seed::{fetch::ResponseDataResult, prelude::Orders};
 use serde::Deserialize;
-use std::{borrow::Cow, num::NonZeroUsize};
+use std::{borrow::Cow, num::NonZeroUsize, time::Duration};
 
 lazy_static! {
     static ref ARTICLES_PER_PAGE: NonZeroUsize = NonZeroUsize::new(5).unwrap();
 }
 
+/// Feeds change often - new articles and favorites.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct RootDecoder {
@@ -68,26 +69,29 @@ pub fn request_url(
 }
 
 #[allow(clippy::type_complexity)]
-pub async fn load_for_profile<Ms: 'static>(
+pub fn load_for_profile<Ms: 'static, GMs: 'static>(
+    orders: &mut impl Orders<Ms, GMs>,
     viewer: Option<Viewer>,
     username: Username<'static>,
     selected_feed: SelectedFeed,
     page_number: PageNumber,
-    f: fn(
-        Result<PaginatedList<Article>, (Username<'static>, Vec<ErrorMessage>)>,
-    ) -> Ms,
-) -> Result<Ms, Ms> {
-    request::new(
+    f: impl Fn(Result<PaginatedList<Article>, (Username<'static>, ApiError)>) -> Ms
+        + 'static,
+) {
+    request::get(
         &request_url(&username, selected_feed, page_number),
         viewer.as_ref(),
     )
-    .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-        f(data_result
-            .map(move |root_decoder| {
-                root_decoder.into_paginated_list(viewer.as_ref())
-            })
-            .map_err(request::fail_reason_into_errors)
-            .map_err(|errors| (username, errors)))
-    })
-    .await
+    .cache(CACHE_TTL)
+    .perform_json_data(
+        orders,
+        move |data_result: ResponseDataResult<RootDecoder>| {
+            f(data_result
+                .map(|root_decoder| {
+                    root_decoder.into_paginated_list(viewer.as_ref())
+                })
+                .map_err(request::fail_reason_into_api_error)
+                .map_err(|error| (username.clone(), error)))
+        },
+    );
 }
@@ -1,6 +1,6 @@
 use crate::{
     coder::decoder,
-    entity::{username, Author, ErrorMessage, Viewer},
+    entity::{username, ApiError, Author, Viewer},
     request,
 };
 use seed::fetch::{Method, ResponseDataResult};
@@ -16,20 +16,20 @@ struct RootDecoder {
 pub fn follow<Ms: 'static>(
     viewer: Option<Viewer>,
     username: &username::Username<'_>,
-    f: fn(Result<Author, Vec<ErrorMessage>>) -> Ms,
+    f: impl FnOnce(Result<Author, ApiError>) -> Ms + 'static,
 ) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new(
-        &format!("profiles/{}/follow", username.as_str()),
-        viewer.as_ref(),
-    )
-    .method(Method::Post)
-    .fetch_json_data(
-        move |data_result: ResponseDataResult<RootDecoder>| {
+    let profile_path = format!("profiles/{}", username.as_str());
+    request::new(&format!("{}/follow", profile_path), viewer.as_ref())
+        .method(Method::Post)
+        .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
+            if data_result.is_ok() {
+                request::invalidate_cache(&profile_path);
+                request::invalidate_cache("articles");
+            }
             f(data_result
                 .map(move |root_decoder| {
                     root_decoder.profile.into_author(viewer.map(Cow::Owned))
                 })
-                .map_err(request::fail_reason_into_errors))
-        },
-    )
+                .map_err(request::fail_reason_into_api_error))
+        })
 }
@@ -1,9 +1,6 @@
 use crate::{
     coder::decoder,
-    entity::{
-        form::settings::{Form, Problem},
-        Viewer,
-    },
+    entity::{form::settings::Form, ApiError, Viewer},
     request,
 };
 use seed::fetch::ResponseDataResult;
@@ -18,13 +15,13 @@ struct RootDecoder {
 
 pub fn load<Ms: 'static>(
     viewer: Option<&Viewer>,
-    f: fn(Result<Form, Vec<Problem>>) -> Ms,
+    f: fn(Result<Form, ApiError>) -> Ms,
 ) -> impl Future<Output = Result<Ms, Ms>> {
-    request::new("user", viewer).fetch_json_data(
+    request::get("user", viewer).fetch_json_data(
         move |data_result: ResponseDataResult<RootDecoder>| {
             f(data_result
                 .map(|root_decoder| root_decoder.user.into_form())
-                .map_err(request::fail_reason_into_problems))
+                .map_err(request::fail_reason_into_api_error))
         },
     )
 }
@@ -1,12 +1,19 @@
 use crate::{
     entity::{
         article::tag::{IntoTags, Tag},
-        ErrorMessage,
+        ApiError,
     },
     request,
 };
-use seed::fetch::ResponseDataResult;
+use seed::{fetch::ResponseDataResult, prelude::Orders};
 use serde::Deserialize;
+use std::time::Duration;
+
+/// Tags change rarely.
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+// Tags are only in the sidebar - don't keep it loading for long.
+const MAX_RETRIES: u32 = 1;
+const RETRY_DELAY: Duration = Duration::from_millis(100);
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -14,14 +21,20 @@ struct RootDecoder {
     tags: Vec<String>,
 }
 
-pub async fn load_list<Ms: 'static>(
-    f: fn(Result<Vec<Tag>, Vec<ErrorMessage>>) -> Ms,
-) -> Result<Ms, Ms> {
-    request::new("tags", None)
-        .fetch_json_data(move |data_result: ResponseDataResult<RootDecoder>| {
-            f(data_result
-                .map(|root_decoder| root_decoder.tags.into_tags())
-                .map_err(request::fail_reason_into_errors))
-        })
-        .await
+pub fn load_list<Ms: 'static, GMs: 'static>(
+    orders: &mut impl Orders<Ms, GMs>,
+    f: fn(Result<Vec<Tag>, ApiError>) -> Ms,
+) {
+    request::get("tags", None)
+        .cache(CACHE_TTL)
+        .retries(MAX_RETRIES)
+        .retry_delay(RETRY_DELAY)
+        .perform_json_data(
+            orders,
+            move |data_result: ResponseDataResult<RootDecoder>| {
+                f(data_result
+                    .map(|root_decoder| root_decoder.tags.into_tags())
+                    .map_err(request::fail_reason_into_api_error))
+            },
+        );
 }
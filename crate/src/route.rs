@@ -1,10 +1,15 @@
-use std::{borrow::Cow, convert::TryFrom, fmt};
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+    fmt,
+};
 
 use seed::prelude::*;
 
 use crate::{
-    entity::{Slug, Username},
-    GMsg,
+    config,
+    entity::{CommentId, PageNumber, Slug, Tag, Username},
+    scroll_restoration, GMsg,
 };
 
 pub fn go_to<Ms: 'static>(
@@ -15,48 +20,172 @@ pub fn go_to<Ms: 'static>(
     orders.send_g_msg(GMsg::RoutePushed(route));
 }
 
+/// Like `go_to`, but the route replaces the current history entry,
+/// so the browser's back button skips the redirected URL.
+pub fn redirect_to<Ms: 'static>(
+    route: Route<'static>,
+    orders: &mut impl Orders<Ms, GMsg>,
+) {
+    // the same state as `seed::push_route` stores for popstate events
+    let state = serde_json::to_string(&seed::Url::from(route.clone()))
+        .expect("serialize route URL failed");
+    seed::history()
+        .replace_state_with_url(
+            &JsValue::from_str(&state),
+            "",
+            Some(&route.to_string()),
+        )
+        .expect("replace history state failed");
+    orders.send_g_msg(GMsg::RoutePushed(route));
+}
+
+/// Push the route to the browser history without changing the page,
+/// e.g. to reflect the page state in the URL.
+pub fn push(route: Route<'static>) {
+    seed::push_route(route);
+    scroll_restoration::mark_current_entry();
+}
+
 // ------ Route ------
 
 #[derive(Clone, Debug)]
 pub enum Route<'a> {
-    Home,
+    Home(HomeQuery),
     Root,
-    Login,
+    /// The route to return to after login.
+    Login(Option<Box<Route<'static>>>),
     Logout,
+    /// Make another saved account active.
+    SwitchAccount(Cow<'a, Username<'a>>),
     Register,
     Settings,
-    Article(Slug),
-    Profile(Cow<'a, Username<'a>>),
+    /// The comment to scroll to is in the URL fragment.
+    Article(Slug, Option<CommentId>),
+    Profile(Cow<'a, Username<'a>>, ProfileQuery),
     NewArticle,
     EditArticle(Slug),
+    /// Unsaved article editor forms.
+    Drafts,
 }
 
 impl<'a> Route<'a> {
+    pub const fn access(&self) -> Access {
+        use Route::*;
+        match self {
+            Register => Access::GuestOnly,
+            Settings | NewArticle | Drafts => Access::AuthRequired,
+            EditArticle(_) => Access::OwnerRequired,
+            // `Login` is public - logged-in users use it to add accounts
+            Home(_) | Root | Login(_) | Logout | SwitchAccount(_)
+            | Article(..) | Profile(..) => Access::Public,
+        }
+    }
+
+    pub fn to_static(&self) -> Route<'static> {
+        use Route::*;
+        match self {
+            Home(query) => Home(query.clone()),
+            Root => Root,
+            Login(redirect) => Login(redirect.clone()),
+            Logout => Logout,
+            SwitchAccount(username) => {
+                SwitchAccount(Cow::Owned(username.to_static()))
+            },
+            Register => Register,
+            Settings => Settings,
+            Article(slug, comment_id) => {
+                Article(slug.clone(), comment_id.clone())
+            },
+            Profile(username, query) => {
+                Profile(Cow::Owned(username.to_static()), *query)
+            },
+            NewArticle => NewArticle,
+            EditArticle(slug) => EditArticle(slug.clone()),
+            Drafts => Drafts,
+        }
+    }
+
     pub fn path(&self) -> Vec<&str> {
         use Route::*;
         match self {
-            Home | Root => vec![],
-            Login => vec!["login"],
+            Home(_) | Root => vec![],
+            Login(_) => vec!["login"],
             Logout => vec!["logout"],
+            SwitchAccount(username) => {
+                vec!["switch-account", username.as_str()]
+            },
             Register => vec!["register"],
             Settings => vec!["settings"],
-            Article(slug) => vec!["article", slug.as_str()],
-            Profile(username) => vec!["profile", username.as_str()],
+            Article(slug, _) => vec!["article", slug.as_str()],
+            Profile(username, _) => vec!["profile", username.as_str()],
             NewArticle => vec!["editor"],
             EditArticle(slug) => vec!["editor", slug.as_str()],
+            Drafts => vec!["drafts"],
+        }
+    }
+
+    pub fn search(&self) -> Option<String> {
+        use Route::*;
+        let parameters = match self {
+            Login(Some(redirect)) => {
+                vec![(REDIRECT_PARAM, redirect.to_string())]
+            },
+            Home(query) => query.parameters(),
+            Profile(_, query) => query.parameters(),
+            _ => Vec::new(),
+        };
+        if parameters.is_empty() {
+            return None;
+        }
+        Some(
+            parameters
+                .into_iter()
+                .map(|(name, value)| {
+                    format!(
+                        "{}={}",
+                        name,
+                        String::from(js_sys::encode_uri_component(&value))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("&"),
+        )
+    }
+
+    pub fn hash(&self) -> Option<String> {
+        match self {
+            Route::Article(_, Some(comment_id)) => Some(comment_id.anchor()),
+            _ => None,
         }
     }
 }
 
 impl<'a> fmt::Display for Route<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "/{}", self.path().join("/"))
+        write!(f, "{}/{}", config::current().base_path, self.path().join("/"))?;
+        if let Some(search) = self.search() {
+            write!(f, "?{}", search)?;
+        }
+        match self.hash() {
+            Some(hash) => write!(f, "#{}", hash),
+            None => Ok(()),
+        }
     }
 }
 
 impl<'a> From<Route<'a>> for seed::Url {
     fn from(route: Route) -> Self {
-        route.path().into()
+        let config = config::current();
+        let mut url = Self::new(
+            base_path_segments(&config.base_path).chain(route.path()).collect(),
+        );
+        if let Some(search) = route.search() {
+            url = url.search(&search);
+        }
+        match route.hash() {
+            Some(hash) => url.hash(&hash),
+            None => url,
+        }
     }
 }
 
@@ -64,37 +193,243 @@ impl<'a> TryFrom<seed::Url> for Route<'a> {
     type Error = ();
 
     fn try_from(url: seed::Url) -> Result<Self, Self::Error> {
-        let mut path = url.path.into_iter();
+        let search = url.search;
+        let hash = url.hash;
+        let mut path = strip_base_path(url.path, &config::current().base_path)
+            .ok_or(())?
+            .into_iter();
 
         match path.next().as_ref().map(String::as_str) {
-            None | Some("") => Some(Route::Home),
-            Some("login") => Some(Route::Login),
+            None | Some("") => {
+                Some(Route::Home(HomeQuery::from_search(search.as_ref())))
+            },
+            Some("login") => Some(Route::Login(
+                redirect_route(search.as_ref()).map(Box::new),
+            )),
             Some("logout") => Some(Route::Logout),
+            Some("switch-account") => path
+                .next()
+                .filter(|username| !username.is_empty())
+                .map(Username::from)
+                .map(Cow::Owned)
+                .map(Route::SwitchAccount),
             Some("settings") => Some(Route::Settings),
             Some("profile") => path
                 .next()
                 .filter(|username| !username.is_empty())
                 .map(Username::from)
-                .map(Cow::Owned)
-                .map(Route::Profile),
+                .map(|username| {
+                    Route::Profile(
+                        Cow::Owned(username),
+                        ProfileQuery::from_search(search.as_ref()),
+                    )
+                }),
             Some("register") => Some(Route::Register),
-            Some("article") => path
-                .next()
-                .filter(|slug| !slug.is_empty())
-                .map(Slug::from)
-                .map(Route::Article),
+            Some("article") => {
+                path.next().filter(|slug| !slug.is_empty()).map(Slug::from).map(
+                    |slug| {
+                        Route::Article(
+                            slug,
+                            hash.as_ref()
+                                .and_then(|hash| CommentId::from_anchor(hash)),
+                        )
+                    },
+                )
+            },
             Some("editor") => path
                 .next()
                 .filter(|slug| !slug.is_empty())
                 .map(Slug::from)
                 .map(Route::EditArticle)
                 .or_else(|| Some(Route::NewArticle)),
+            Some("drafts") => Some(Route::Drafts),
             _ => None,
         }
         .ok_or(())
     }
 }
 
+// ------ Access ------
+
+/// Who can visit the route; see `Route::access`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    /// Logged-in users are redirected to the home page.
+    GuestOnly,
+    /// Guests are redirected to the login page.
+    AuthRequired,
+    /// Only the article author can visit it. Guests are redirected
+    /// to the login page, other users to the article itself.
+    OwnerRequired,
+}
+
+// ------ HomeQuery ------
+
+/// `/?feed=global&page=3` or `/?tag=rust`
+#[derive(Clone, Debug, Default)]
+pub struct HomeQuery {
+    /// `None` means the default feed for the current session.
+    pub feed: Option<HomeFeed>,
+    pub page: PageNumber,
+}
+
+#[derive(Clone, Debug)]
+pub enum HomeFeed {
+    Your,
+    Global,
+    Tag(Tag),
+}
+
+impl HomeQuery {
+    fn from_search(search: Option<&String>) -> Self {
+        let mut feed = None;
+        let mut tag = None;
+        let mut page = PageNumber::default();
+        for (name, value) in parameters(search) {
+            match (name, value.as_str()) {
+                (FEED_PARAM, "your") => feed = Some(HomeFeed::Your),
+                (FEED_PARAM, "global") => feed = Some(HomeFeed::Global),
+                (TAG_PARAM, _) if !value.is_empty() => {
+                    tag = Some(Tag::from(value))
+                },
+                (PAGE_PARAM, _) => {
+                    page = parse_page_number(&value).unwrap_or_default()
+                },
+                _ => (),
+            }
+        }
+        Self {
+            // the tag feed wins over the `feed` parameter
+            feed: tag.map(HomeFeed::Tag).or(feed),
+            page,
+        }
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        let mut parameters = Vec::new();
+        match &self.feed {
+            Some(HomeFeed::Your) => {
+                parameters.push((FEED_PARAM, "your".to_owned()))
+            },
+            Some(HomeFeed::Global) => {
+                parameters.push((FEED_PARAM, "global".to_owned()))
+            },
+            Some(HomeFeed::Tag(tag)) => {
+                parameters.push((TAG_PARAM, tag.to_string()))
+            },
+            None => (),
+        }
+        push_page_parameter(&mut parameters, self.page);
+        parameters
+    }
+}
+
+// ------ ProfileQuery ------
+
+/// `/profile/john?tab=favorites&page=2`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProfileQuery {
+    pub tab: ProfileTab,
+    pub page: PageNumber,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfileTab {
+    #[default]
+    Articles,
+    Favorites,
+}
+
+impl ProfileQuery {
+    fn from_search(search: Option<&String>) -> Self {
+        let mut query = Self::default();
+        for (name, value) in parameters(search) {
+            match (name, value.as_str()) {
+                (TAB_PARAM, "articles") => query.tab = ProfileTab::Articles,
+                (TAB_PARAM, "favorites") => query.tab = ProfileTab::Favorites,
+                (PAGE_PARAM, _) => {
+                    query.page = parse_page_number(&value).unwrap_or_default()
+                },
+                _ => (),
+            }
+        }
+        query
+    }
+
+    fn parameters(self) -> Vec<(&'static str, String)> {
+        let mut parameters = Vec::new();
+        if self.tab == ProfileTab::Favorites {
+            parameters.push((TAB_PARAM, "favorites".to_owned()));
+        }
+        push_page_parameter(&mut parameters, self.page);
+        parameters
+    }
+}
+
+// ====== PRIVATE ======
+
+const REDIRECT_PARAM: &str = "redirect";
+const FEED_PARAM: &str = "feed";
+const TAG_PARAM: &str = "tag";
+const TAB_PARAM: &str = "tab";
+const PAGE_PARAM: &str = "page";
+
+/// Decoded query parameters; parameters with invalid encoding are skipped.
+fn parameters(search: Option<&String>) -> impl Iterator<Item = (&str, String)> {
+    search.into_iter().flat_map(|search| search.split('&')).filter_map(
+        |parameter| {
+            let mut parameter = parameter.splitn(2, '=');
+            let name = parameter.next()?;
+            let value =
+                js_sys::decode_uri_component(parameter.next().unwrap_or(""))
+                    .ok()?
+                    .into();
+            Some((name, value))
+        },
+    )
+}
+
+fn parse_page_number(value: &str) -> Option<PageNumber> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|page_number| *page_number > 0)
+        .map(PageNumber::from)
+}
+
+/// The first page is the default one, so it isn't pushed.
+fn push_page_parameter(
+    parameters: &mut Vec<(&'static str, String)>,
+    page_number: PageNumber,
+) {
+    if page_number != PageNumber::default() {
+        parameters.push((PAGE_PARAM, page_number.to_string()));
+    }
+}
+
+fn base_path_segments(base_path: &str) -> impl Iterator<Item = &str> {
+    base_path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// `None` if the `path` is outside of the `base_path`.
+fn strip_base_path(path: Vec<String>, base_path: &str) -> Option<Vec<String>> {
+    let mut path = path.into_iter();
+    for base_segment in base_path_segments(base_path) {
+        if path.next()? != base_segment {
+            return None;
+        }
+    }
+    Some(path.collect())
+}
+
+fn redirect_route(search: Option<&String>) -> Option<Route<'static>> {
+    let redirect = parameters(search)
+        .find(|(name, _)| *name == REDIRECT_PARAM)
+        .map(|(_, value)| value)?;
+    seed::Url::try_from(redirect).ok()?.try_into().ok()
+}
+
 // ====== ====== TESTS ====== ======
 
 #[cfg(test)]
@@ -114,29 +449,77 @@ pub mod tests {
         let route = url.try_into();
 
         // ====== ASSERT ======
-        assert!(if let Ok(Route::Home) = route {
-            true
+        assert!(matches!(route, Ok(Route::Home(_))));
+    }
+
+    #[wasm_bindgen_test]
+    fn home_route_trailing_slash_test() {
+        // ====== ARRANGE ======
+        let url = seed::Url::new(vec![""]);
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(matches!(route, Ok(Route::Home(_))));
+    }
+
+    #[wasm_bindgen_test]
+    fn home_route_with_query_test() {
+        // ====== ARRANGE ======
+        let url =
+            seed::Url::new(vec![""]).search("feed=global&tag=rust&page=3");
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(if let Ok(Route::Home(HomeQuery {
+            feed: Some(HomeFeed::Tag(tag)),
+            page,
+        })) = route
+        {
+            tag.to_string() == "rust" && *page == 3
         } else {
             false
         })
     }
 
     #[wasm_bindgen_test]
-    fn home_route_trailing_slash_test() {
+    fn home_route_with_invalid_query_test() {
         // ====== ARRANGE ======
-        let url = seed::Url::new(vec![""]);
+        let url = seed::Url::new(vec![""]).search("feed=unknown&page=0");
 
         // ====== ACT ======
         let route = url.try_into();
 
         // ====== ASSERT ======
-        assert!(if let Ok(Route::Home) = route {
-            true
+        assert!(if let Ok(Route::Home(HomeQuery {
+            feed: None,
+            page,
+        })) = route
+        {
+            page == PageNumber::default()
         } else {
             false
         })
     }
 
+    #[wasm_bindgen_test]
+    fn home_route_with_query_to_string_test() {
+        // ====== ARRANGE ======
+        let route = Route::Home(HomeQuery {
+            feed: Some(HomeFeed::Tag(Tag::from("c++".to_owned()))),
+            page: PageNumber::from(2),
+        });
+
+        // ====== ACT ======
+        let url = route.to_string();
+
+        // ====== ASSERT ======
+        assert_eq!(url, "/?tag=c%2B%2B&page=2");
+    }
+
     #[wasm_bindgen_test]
     fn login_route_test() {
         // ====== ARRANGE ======
@@ -146,13 +529,43 @@ pub mod tests {
         let route = url.try_into();
 
         // ====== ASSERT ======
-        assert!(if let Ok(Route::Login) = route {
-            true
+        assert!(matches!(route, Ok(Route::Login(None))));
+    }
+
+    #[wasm_bindgen_test]
+    fn login_route_with_redirect_test() {
+        // ====== ARRANGE ======
+        let url = seed::Url::new(vec!["login"])
+            .search("redirect=%2Farticle%2Fmy_article");
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(if let Ok(Route::Login(Some(redirect))) = route {
+            if let Route::Article(slug, _) = *redirect {
+                slug.as_str() == "my_article"
+            } else {
+                false
+            }
         } else {
             false
         })
     }
 
+    #[wasm_bindgen_test]
+    fn login_route_with_redirect_to_string_test() {
+        // ====== ARRANGE ======
+        let slug = Slug::from("my_article".to_owned());
+        let route = Route::Login(Some(Box::new(Route::Article(slug, None))));
+
+        // ====== ACT ======
+        let url = route.to_string();
+
+        // ====== ASSERT ======
+        assert_eq!(url, "/login?redirect=%2Farticle%2Fmy_article");
+    }
+
     #[wasm_bindgen_test]
     fn logout_route_test() {
         // ====== ARRANGE ======
@@ -169,6 +582,22 @@ pub mod tests {
         })
     }
 
+    #[wasm_bindgen_test]
+    fn switch_account_route_test() {
+        // ====== ARRANGE ======
+        let url = seed::Url::new(vec!["switch-account", "john"]);
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(if let Ok(Route::SwitchAccount(username)) = route {
+            username.as_str() == "john"
+        } else {
+            false
+        })
+    }
+
     #[wasm_bindgen_test]
     fn settings_route_test() {
         // ====== ARRANGE ======
@@ -194,13 +623,46 @@ pub mod tests {
         let route = url.try_into();
 
         // ====== ASSERT ======
-        assert!(if let Ok(Route::Profile(username)) = route {
+        assert!(if let Ok(Route::Profile(username, _)) = route {
             username.as_str() == "john"
         } else {
             false
         })
     }
 
+    #[wasm_bindgen_test]
+    fn profile_route_with_query_test() {
+        // ====== ARRANGE ======
+        let url = seed::Url::new(vec!["profile", "john"])
+            .search("tab=favorites&page=2");
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(if let Ok(Route::Profile(username, query)) = route {
+            username.as_str() == "john"
+                && query.tab == ProfileTab::Favorites
+                && *query.page == 2
+        } else {
+            false
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn profile_route_with_default_query_to_string_test() {
+        // ====== ARRANGE ======
+        let username = Username::from("john".to_owned());
+        let route =
+            Route::Profile(Cow::Owned(username), ProfileQuery::default());
+
+        // ====== ACT ======
+        let url = route.to_string();
+
+        // ====== ASSERT ======
+        assert_eq!(url, "/profile/john");
+    }
+
     #[wasm_bindgen_test]
     fn register_route_test() {
         // ====== ARRANGE ======
@@ -226,13 +688,43 @@ pub mod tests {
         let route = url.try_into();
 
         // ====== ASSERT ======
-        assert!(if let Ok(Route::Article(slug)) = route {
+        assert!(if let Ok(Route::Article(slug, None)) = route {
             slug.as_str() == "my_article"
         } else {
             false
         })
     }
 
+    #[wasm_bindgen_test]
+    fn article_route_with_comment_test() {
+        // ====== ARRANGE ======
+        let url =
+            seed::Url::new(vec!["article", "my_article"]).hash("comment-42");
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(if let Ok(Route::Article(slug, Some(comment_id))) = route {
+            slug.as_str() == "my_article" && comment_id == CommentId::from(42)
+        } else {
+            false
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn article_route_with_comment_to_string_test() {
+        // ====== ARRANGE ======
+        let slug = Slug::from("my_article".to_owned());
+        let route = Route::Article(slug, Some(CommentId::from(42)));
+
+        // ====== ACT ======
+        let url = route.to_string();
+
+        // ====== ASSERT ======
+        assert_eq!(url, "/article/my_article#comment-42");
+    }
+
     #[wasm_bindgen_test]
     fn edit_article_route_test() {
         // ====== ARRANGE ======
@@ -269,6 +761,54 @@ pub mod tests {
         )
     }
 
+    #[wasm_bindgen_test]
+    fn drafts_route_test() {
+        // ====== ARRANGE ======
+        let url = seed::Url::new(vec!["drafts"]);
+
+        // ====== ACT ======
+        let route = url.try_into();
+
+        // ====== ASSERT ======
+        assert!(matches!(route, Ok(Route::Drafts)));
+    }
+
+    #[wasm_bindgen_test]
+    fn access_test() {
+        // ====== ARRANGE ======
+        let slug = Slug::from("my_article".to_owned());
+
+        // ====== ACT & ASSERT ======
+        assert_eq!(Route::Register.access(), Access::GuestOnly);
+        assert_eq!(Route::Login(None).access(), Access::Public);
+        assert_eq!(Route::Settings.access(), Access::AuthRequired);
+        assert_eq!(Route::NewArticle.access(), Access::AuthRequired);
+        assert_eq!(Route::EditArticle(slug).access(), Access::OwnerRequired);
+    }
+
+    #[wasm_bindgen_test]
+    fn strip_base_path_test() {
+        // ====== ARRANGE ======
+        let path = |segments: &[&str]| {
+            segments.iter().map(|segment| (*segment).to_owned()).collect()
+        };
+
+        // ====== ACT & ASSERT ======
+        assert_eq!(
+            strip_base_path(path(&["conduit", "article", "a"]), "/conduit"),
+            Some(path(&["article", "a"]))
+        );
+        assert_eq!(
+            strip_base_path(path(&["conduit"]), "/conduit"),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            strip_base_path(path(&["article", "a"]), ""),
+            Some(path(&["article", "a"]))
+        );
+        assert_eq!(strip_base_path(path(&["article", "a"]), "/conduit"), None);
+    }
+
     #[wasm_bindgen_test]
     fn invalid_route_test() {
         // ====== ARRANGE ======
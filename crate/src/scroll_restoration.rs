@@ -0,0 +1,108 @@
+use crate::logger;
+use seed::prelude::*;
+use serde_json::Value;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+use wasm_bindgen::JsCast;
+
+/// The field added to the history state stored by `seed::push_route`.
+const KEY_FIELD: &str = "scroll_key";
+
+thread_local! {
+    /// History entry key -> vertical scroll position.
+    static POSITIONS: RefCell<HashMap<String, f64>> = RefCell::new(HashMap::new());
+    static KEY_COUNTER: Cell<u32> = const { Cell::new(0) };
+    /// The position waiting for the current page to be rendered.
+    static PENDING_POSITION: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+/// Take over scroll restoration from the browser
+/// and remember the scroll position of the current history entry.
+/// It should be called once, before the first route change.
+pub fn init() {
+    if let Err(error) = seed::history()
+        .set_scroll_restoration(web_sys::ScrollRestoration::Manual)
+    {
+        logger::error(error);
+    }
+    let closure = Closure::wrap(Box::new(|| {
+        if let (Some(key), Ok(position)) =
+            (current_key(), seed::window().page_y_offset())
+        {
+            POSITIONS
+                .with(|positions| positions.borrow_mut().insert(key, position));
+        }
+    }) as Box<dyn Fn()>);
+    seed::window()
+        .add_event_listener_with_callback(
+            "scroll",
+            closure.as_ref().unchecked_ref(),
+        )
+        .expect("add scroll event listener failed");
+    // the listener lives as long as the app
+    closure.forget();
+}
+
+/// Prepare the saved position of a revisited history entry for `restore`
+/// or mark a new history entry.
+pub fn on_route_changed() {
+    let position = current_key().and_then(|key| {
+        POSITIONS.with(|positions| positions.borrow().get(&key).copied())
+    });
+    PENDING_POSITION.with(|pending_position| pending_position.set(position));
+    mark_current_entry();
+}
+
+/// Mark the current history entry so its scroll position can be saved.
+/// Entries pushed by `seed::push_route` or the browser aren't marked.
+pub fn mark_current_entry() {
+    if current_key().is_some() {
+        return;
+    }
+    let history = seed::history();
+    // the landing page entry has no state
+    let state = history
+        .state()
+        .ok()
+        .and_then(|state| state.as_string())
+        .and_then(|state| serde_json::from_str(&state).ok())
+        .or_else(|| serde_json::to_value(seed::browser::url::current()).ok());
+
+    if let Some(Value::Object(mut state)) = state {
+        state.insert(KEY_FIELD.to_owned(), Value::String(new_key()));
+        let state = Value::Object(state).to_string();
+        if let Err(error) =
+            history.replace_state(&JsValue::from_str(&state), "")
+        {
+            logger::error(error);
+        }
+    }
+}
+
+/// Scroll to the prepared position, if there is any.
+/// It is called once the page is rendered with its data.
+pub fn restore() {
+    if let Some(position) = PENDING_POSITION.with(Cell::take) {
+        seed::window().scroll_to_with_x_and_y(0., position);
+    }
+}
+
+// ====== PRIVATE ======
+
+fn current_key() -> Option<String> {
+    let state = seed::history().state().ok()?.as_string()?;
+    let state = serde_json::from_str::<Value>(&state).ok()?;
+    state.get(KEY_FIELD)?.as_str().map(ToOwned::to_owned)
+}
+
+/// Keys have to be unique across page reloads,
+/// because the browser keeps history states.
+fn new_key() -> String {
+    let counter = KEY_COUNTER.with(|counter| {
+        counter.set(counter.get() + 1);
+        counter.get()
+    });
+    format!("{}-{}", js_sys::Date::now(), counter)
+}
@@ -1,33 +1,390 @@
-use crate::entity::Viewer;
+use crate::{
+    entity::{form::article_editor::Form, Slug, Username, Viewer},
+    i18n::Language,
+    outbox::{Mutation, ReplayLock},
+};
+use indexmap::IndexMap;
 use seed::storage;
-use serde_json;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{self, json, Value};
+use std::{collections::VecDeque, convert::TryFrom};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 
-const STORAGE_KEY: &str = "conduit";
+// Items are saved in an envelope `{ "version": 1, "data": .. }`.
+// The version is the number of migrations of the item, so a change of the
+// data shape (e.g. `Viewer`) requires a new migration in the item's list.
+// Data saved before the envelope was introduced have version 0.
 
-pub fn load_viewer() -> Option<Viewer> {
-    local_storage()
-        .get_item(STORAGE_KEY)
-        .expect("try to get local storage item failed")
-        .map(|serialized_item| {
-            serde_json::from_str(&serialized_item)
-                .expect("viewer deserialization failed")
+const ACCOUNTS: Item = Item {
+    key: "conduit",
+    migrations: &[from_unversioned, into_accounts],
+};
+
+const OUTBOX: Item = Item {
+    key: "conduit_outbox",
+    migrations: &[from_unversioned],
+};
+
+/// The tab that is sending the outbox, see `ReplayLock`.
+const OUTBOX_LOCK: Item = Item {
+    key: "conduit_outbox_lock",
+    migrations: &[],
+};
+
+/// Forms keyed by username and then by article slug
+/// or `NEW_ARTICLE_DRAFT_KEY`.
+const DRAFTS: Item = Item {
+    key: "conduit_drafts",
+    migrations: &[into_drafts_by_username],
+};
+
+const NEW_ARTICLE_DRAFT_KEY: &str = "new";
+
+const LANGUAGE: Item = Item {
+    key: "conduit_language",
+    migrations: &[],
+};
+
+// ------ StorageError ------
+
+#[derive(Debug)]
+pub enum StorageError {
+    /// Local storage is disabled or not supported by the browser.
+    Unavailable,
+    /// The browser has refused the operation (e.g. the quota is exceeded).
+    Access(String),
+    /// Data can't be (de)serialized or migrated.
+    InvalidData(String),
+    /// Data have been saved by a newer version of the app.
+    UnsupportedVersion(usize),
+}
+
+impl StorageError {
+    fn new_access(error: &JsValue) -> Self {
+        Self::Access(format!("{:?}", error))
+    }
+}
+
+// ------ Accounts ------
+
+/// All viewers that have logged in; the active one is the current viewer.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Accounts {
+    viewers: Vec<Viewer>,
+    active: Option<Username<'static>>,
+}
+
+impl Accounts {
+    pub fn into_viewers(self) -> Vec<Viewer> {
+        self.viewers
+    }
+
+    fn into_active_viewer(self) -> Option<Viewer> {
+        let active = self.active?;
+        self.viewers.into_iter().find(|viewer| viewer.username() == &active)
+    }
+}
+
+pub fn load_accounts() -> Result<Accounts, StorageError> {
+    load(&ACCOUNTS).map(Option::unwrap_or_default)
+}
+
+// ------ viewer ------
+
+pub fn load_viewer() -> Result<Option<Viewer>, StorageError> {
+    load_accounts().map(Accounts::into_active_viewer)
+}
+
+/// Add or update the viewer and make it active.
+pub fn store_viewer(viewer: &Viewer) -> Result<(), StorageError> {
+    let mut accounts = load_accounts()?;
+    match accounts
+        .viewers
+        .iter_mut()
+        .find(|account| account.username() == viewer.username())
+    {
+        Some(account) => *account = viewer.clone(),
+        None => accounts.viewers.push(viewer.clone()),
+    }
+    accounts.active = Some(viewer.username().to_static());
+    store(&ACCOUNTS, &accounts)
+}
+
+/// Make the viewer with `username` active.
+/// Returns `None` if there is no such account.
+pub fn activate_viewer(
+    username: &Username,
+) -> Result<Option<Viewer>, StorageError> {
+    let mut accounts = load_accounts()?;
+    let viewer = accounts
+        .viewers
+        .iter()
+        .find(|viewer| viewer.username() == username)
+        .cloned();
+    if viewer.is_some() {
+        accounts.active = Some(username.to_static());
+        store(&ACCOUNTS, &accounts)?;
+    }
+    Ok(viewer)
+}
+
+/// Remove the active viewer and its pending outbox.
+/// Other accounts and all drafts are kept.
+pub fn remove_active_viewer() -> Result<(), StorageError> {
+    let mut accounts = load_accounts()?;
+    if let Some(active) = accounts.active.take() {
+        accounts.viewers.retain(|viewer| viewer.username() != &active);
+    }
+    store(&ACCOUNTS, &accounts)?;
+    local_storage()?
+        .remove_item(OUTBOX.key)
+        .map_err(|error| StorageError::new_access(&error))
+}
+
+/// Call `handler` when another tab changes stored accounts
+/// (e.g. the user has logged in or out there).
+pub fn on_viewer_changed(handler: impl Fn() + 'static) {
+    on_item_changed(&ACCOUNTS, handler);
+}
+
+// ------ outbox ------
+
+pub fn load_outbox() -> Result<VecDeque<Mutation>, StorageError> {
+    load(&OUTBOX).map(Option::unwrap_or_default)
+}
+
+pub fn store_outbox(
+    mutations: &VecDeque<Mutation>,
+) -> Result<(), StorageError> {
+    store(&OUTBOX, mutations)
+}
+
+/// Call `handler` when another tab changes the stored outbox.
+pub fn on_outbox_changed(handler: impl Fn() + 'static) {
+    on_item_changed(&OUTBOX, handler);
+}
+
+pub fn load_outbox_lock() -> Result<Option<ReplayLock>, StorageError> {
+    load(&OUTBOX_LOCK)
+}
+
+pub fn store_outbox_lock(lock: &ReplayLock) -> Result<(), StorageError> {
+    store(&OUTBOX_LOCK, lock)
+}
+
+pub fn remove_outbox_lock() -> Result<(), StorageError> {
+    local_storage()?
+        .remove_item(OUTBOX_LOCK.key)
+        .map_err(|error| StorageError::new_access(&error))
+}
+
+// ------ language ------
+
+pub fn load_language() -> Result<Option<Language>, StorageError> {
+    load(&LANGUAGE)
+}
+
+pub fn store_language(language: Language) -> Result<(), StorageError> {
+    store(&LANGUAGE, &language)
+}
+
+// ------ drafts ------
+
+/// Unsaved form from the article editor.
+pub struct Draft {
+    /// `None` for a new article.
+    pub slug: Option<Slug>,
+    pub form: Form,
+}
+
+pub fn load_drafts(username: &Username) -> Result<Vec<Draft>, StorageError> {
+    Ok(load_draft_map(username)?
+        .into_iter()
+        .map(|(key, form)| Draft {
+            slug: Some(key)
+                .filter(|key| key != NEW_ARTICLE_DRAFT_KEY)
+                .map(Slug::from),
+            form,
         })
+        .collect())
 }
 
-pub fn store_viewer(viewer: &Viewer) {
-    storage::store_data(&local_storage(), STORAGE_KEY, viewer);
+pub fn load_draft(
+    username: &Username,
+    slug: Option<&Slug>,
+) -> Result<Option<Form>, StorageError> {
+    Ok(load_draft_map(username)?.swap_remove(draft_key(slug)))
 }
 
-pub fn delete_app_data() {
-    local_storage()
-        .remove_item(STORAGE_KEY)
-        .expect("remove item from local storage failed");
+pub fn store_draft(
+    username: &Username,
+    slug: Option<&Slug>,
+    form: &Form,
+) -> Result<(), StorageError> {
+    let mut drafts = load_draft_map(username)?;
+    drafts.insert(draft_key(slug).to_owned(), form.clone());
+    store_draft_map(username, drafts)
+}
+
+pub fn remove_draft(
+    username: &Username,
+    slug: Option<&Slug>,
+) -> Result<(), StorageError> {
+    let mut drafts = load_draft_map(username)?;
+    if drafts.swap_remove(draft_key(slug)).is_some() {
+        store_draft_map(username, drafts)?;
+    }
+    Ok(())
+}
+
+/// Remove all drafts of the given user (e.g. on an explicit logout).
+pub fn remove_drafts(username: &Username) -> Result<(), StorageError> {
+    store_draft_map(username, DraftMap::new())
 }
 
 // ====== PRIVATE ======
 
-fn local_storage() -> storage::Storage {
-    storage::get_storage().expect("get local storage failed")
+type DraftMap = IndexMap<String, Form>;
+
+fn load_draft_maps() -> Result<IndexMap<String, DraftMap>, StorageError> {
+    load(&DRAFTS).map(Option::unwrap_or_default)
+}
+
+fn load_draft_map(username: &Username) -> Result<DraftMap, StorageError> {
+    Ok(load_draft_maps()?.swap_remove(username.as_str()).unwrap_or_default())
+}
+
+/// Empty `drafts` remove the user's entry.
+fn store_draft_map(
+    username: &Username,
+    drafts: DraftMap,
+) -> Result<(), StorageError> {
+    let mut draft_maps = load_draft_maps()?;
+    if drafts.is_empty() {
+        draft_maps.swap_remove(username.as_str());
+    } else {
+        draft_maps.insert(username.as_str().to_owned(), drafts);
+    }
+    store(&DRAFTS, &draft_maps)
+}
+
+fn draft_key(slug: Option<&Slug>) -> &str {
+    slug.map_or(NEW_ARTICLE_DRAFT_KEY, |slug| slug.as_str())
+}
+
+type Migration = fn(Value) -> Result<Value, String>;
+
+struct Item {
+    key: &'static str,
+    migrations: &'static [Migration],
+}
+
+impl Item {
+    const fn version(&self) -> usize {
+        self.migrations.len()
+    }
+}
+
+fn load<T: DeserializeOwned>(item: &Item) -> Result<Option<T>, StorageError> {
+    let serialized_item = match local_storage()?
+        .get_item(item.key)
+        .map_err(|error| StorageError::new_access(&error))?
+    {
+        Some(serialized_item) => serialized_item,
+        None => return Ok(None),
+    };
+    let value = serde_json::from_str(&serialized_item)
+        .map_err(|error| StorageError::InvalidData(error.to_string()))?;
+
+    let (version, data) = open_envelope(value)?;
+    if version > item.version() {
+        return Err(StorageError::UnsupportedVersion(version));
+    }
+    let data = item.migrations[version..]
+        .iter()
+        .try_fold(data, |data, migrate| migrate(data))
+        .map_err(StorageError::InvalidData)?;
+
+    serde_json::from_value(data)
+        .map(Some)
+        .map_err(|error| StorageError::InvalidData(error.to_string()))
+}
+
+fn store(item: &Item, data: &impl Serialize) -> Result<(), StorageError> {
+    let serialized_item = serde_json::to_string(&json!({
+        "version": item.version(),
+        "data": data,
+    }))
+    .map_err(|error| StorageError::InvalidData(error.to_string()))?;
+
+    local_storage()?
+        .set_item(item.key, &serialized_item)
+        .map_err(|error| StorageError::new_access(&error))
+}
+
+/// Returns the version and the data of the stored item.
+fn open_envelope(value: Value) -> Result<(usize, Value), StorageError> {
+    match value {
+        Value::Object(mut envelope) if envelope.contains_key("version") => {
+            let version = envelope
+                .get("version")
+                .and_then(Value::as_u64)
+                .and_then(|version| usize::try_from(version).ok())
+                .ok_or_else(|| {
+                    StorageError::InvalidData("invalid version".into())
+                })?;
+            let data = envelope.remove("data").unwrap_or(Value::Null);
+            Ok((version, data))
+        },
+        data => Ok((0, data)),
+    }
+}
+
+fn local_storage() -> Result<storage::Storage, StorageError> {
+    storage::get_storage().ok_or(StorageError::Unavailable)
+}
+
+/// `storage` events are fired only in other tabs.
+fn on_item_changed(item: &'static Item, handler: impl Fn() + 'static) {
+    let closure = Closure::wrap(Box::new(move |event: web_sys::StorageEvent| {
+        // the key is `None` when the whole storage has been cleared
+        match event.key() {
+            Some(key) if key != item.key => (),
+            _ => handler(),
+        }
+    }) as Box<dyn Fn(web_sys::StorageEvent)>);
+    seed::window()
+        .add_event_listener_with_callback(
+            "storage",
+            closure.as_ref().unchecked_ref(),
+        )
+        .expect("add storage event listener failed");
+    // the listener lives as long as the app
+    closure.forget();
+}
+
+// ------ migrations ------
+
+/// Version 0 has the same data, only the envelope is missing.
+fn from_unversioned(data: Value) -> Result<Value, String> {
+    Ok(data)
+}
+
+/// Version 1 contains only one viewer.
+fn into_accounts(viewer: Value) -> Result<Value, String> {
+    if viewer.is_null() {
+        return Ok(json!({ "viewers": [], "active": null }));
+    }
+    let username = viewer
+        .pointer("/profile/username")
+        .cloned()
+        .ok_or("viewer's username is missing")?;
+    Ok(json!({ "viewers": [viewer], "active": username }))
+}
+
+/// Version 0 drafts don't belong to any user, so they are dropped.
+fn into_drafts_by_username(_drafts: Value) -> Result<Value, String> {
+    Ok(json!({}))
 }
 
 // ====== ====== TESTS ====== ======
@@ -35,13 +392,38 @@ fn local_storage() -> storage::Storage {
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use crate::entity::{Avatar, Profile, Username};
+    use crate::entity::{Avatar, Profile};
     use wasm_bindgen_test::*;
 
     wasm_bindgen_test_configure!(run_in_browser);
 
     fn clean_local_storage() {
-        local_storage().clear().expect("clear storage failed");
+        local_storage()
+            .expect("get local storage failed")
+            .clear()
+            .expect("clear storage failed");
+    }
+
+    fn set_local_storage_item(key: &str, value: &str) {
+        local_storage()
+            .expect("get local storage failed")
+            .set_item(key, value)
+            .expect("set local storage item failed");
+    }
+
+    fn viewer() -> Viewer {
+        named_viewer("")
+    }
+
+    fn named_viewer(username: &'static str) -> Viewer {
+        Viewer {
+            profile: Profile {
+                username: username.into(),
+                avatar: Avatar::new(None as Option<&str>),
+                bio: None,
+            },
+            auth_token: String::new(),
+        }
     }
 
     #[wasm_bindgen_test]
@@ -50,7 +432,7 @@ pub mod tests {
         clean_local_storage();
 
         // ====== ACT & ASSERT ======
-        assert!(load_viewer().is_none())
+        assert!(load_viewer().expect("load viewer failed").is_none())
     }
 
     #[wasm_bindgen_test]
@@ -58,41 +440,140 @@ pub mod tests {
         // ====== ARRANGE ======
         clean_local_storage();
 
-        let viewer = Viewer {
-            profile: Profile {
-                username: Username::default(),
-                avatar: Avatar::new(None as Option<&str>),
-                bio: None,
-            },
-            auth_token: String::new(),
-        };
-
         // ====== ACT ======
-        store_viewer(&viewer);
+        store_viewer(&viewer()).expect("store viewer failed");
 
         //====== ASSERT ======
-        assert!(load_viewer().is_some());
+        assert!(load_viewer().expect("load viewer failed").is_some());
     }
 
     #[wasm_bindgen_test]
-    fn delete_app_data_test() {
+    fn load_unversioned_viewer_test() {
         // ====== ARRANGE ======
         clean_local_storage();
+        let serialized_viewer =
+            serde_json::to_string(&viewer()).expect("serialize viewer failed");
+        set_local_storage_item(ACCOUNTS.key, &serialized_viewer);
 
-        let viewer = Viewer {
-            profile: Profile {
-                username: Username::default(),
-                avatar: Avatar::new(None as Option<&str>),
-                bio: None,
-            },
-            auth_token: String::new(),
-        };
-        store_viewer(&viewer);
+        // ====== ACT & ASSERT ======
+        assert!(load_viewer().expect("load viewer failed").is_some());
+    }
+
+    #[wasm_bindgen_test]
+    fn load_corrupted_viewer_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        set_local_storage_item(ACCOUNTS.key, r#"{"profile": 1}"#);
+
+        // ====== ACT ======
+        let result = load_viewer();
+
+        // ====== ASSERT ======
+        assert!(matches!(result, Err(StorageError::InvalidData(_))));
+    }
+
+    #[wasm_bindgen_test]
+    fn load_newer_viewer_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        set_local_storage_item(ACCOUNTS.key, r#"{"version": 99, "data": {}}"#);
+
+        // ====== ACT ======
+        let result = load_viewer();
+
+        // ====== ASSERT ======
+        assert!(matches!(result, Err(StorageError::UnsupportedVersion(99))));
+    }
+
+    #[wasm_bindgen_test]
+    fn activate_viewer_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        store_viewer(&named_viewer("john")).expect("store viewer failed");
+        store_viewer(&named_viewer("jane")).expect("store viewer failed");
+
+        // ====== ACT ======
+        activate_viewer(&"john".into()).expect("activate viewer failed");
+
+        // ====== ASSERT ======
+        let viewer = load_viewer().expect("load viewer failed");
+        assert_eq!(
+            viewer.expect("no active viewer").username().as_str(),
+            "john"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_active_viewer_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        store_viewer(&named_viewer("john")).expect("store viewer failed");
+        store_viewer(&named_viewer("jane")).expect("store viewer failed");
+        let (john, jane) = ("john".into(), "jane".into());
+        store_draft(&john, None, &Form::default()).expect("store draft failed");
+        store_draft(&jane, None, &Form::default()).expect("store draft failed");
+
+        // ====== ACT ======
+        remove_active_viewer().expect("remove active viewer failed");
+
+        // ====== ASSERT ======
+        assert!(load_viewer().expect("load viewer failed").is_none());
+        let accounts = load_accounts().expect("load accounts failed");
+        assert_eq!(accounts.into_viewers().len(), 1);
+        assert_eq!(load_drafts(&john).expect("load drafts failed").len(), 1);
+        assert_eq!(load_drafts(&jane).expect("load drafts failed").len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_drafts_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        let (john, jane) = ("john".into(), "jane".into());
+        store_draft(&john, None, &Form::default()).expect("store draft failed");
+        store_draft(&jane, None, &Form::default()).expect("store draft failed");
+
+        // ====== ACT ======
+        remove_drafts(&jane).expect("remove drafts failed");
+
+        // ====== ASSERT ======
+        assert_eq!(load_drafts(&john).expect("load drafts failed").len(), 1);
+        assert!(load_drafts(&jane).expect("load drafts failed").is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_draft_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+        let (john, jane) = ("john".into(), "jane".into());
+        let slug = Slug::from("my_article".to_owned());
+        store_draft(&john, None, &Form::default()).expect("store draft failed");
+        store_draft(&john, Some(&slug), &Form::default())
+            .expect("store draft failed");
+        store_draft(&jane, None, &Form::default()).expect("store draft failed");
+
+        // ====== ACT ======
+        remove_draft(&john, None).expect("remove draft failed");
+
+        // ====== ASSERT ======
+        let drafts = load_drafts(&john).expect("load drafts failed");
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].slug, Some(slug));
+        assert_eq!(load_drafts(&jane).expect("load drafts failed").len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn store_outbox_test() {
+        // ====== ARRANGE ======
+        clean_local_storage();
+
+        let mut mutations = VecDeque::new();
+        mutations.push_back(Mutation::Favorite("slug".to_owned().into()));
+        mutations.push_back(Mutation::Follow("john".into()));
 
         // ====== ACT ======
-        delete_app_data();
+        store_outbox(&mutations).expect("store outbox failed");
 
         // ====== ASSERT ======
-        assert!(load_viewer().is_none());
+        assert_eq!(load_outbox().expect("load outbox failed").len(), 2);
     }
 }
@@ -0,0 +1,206 @@
+use crate::entity::{
+    Article, Author, Comment, CommentId, PaginatedList, Slug, Username,
+};
+use std::collections::HashMap;
+
+// ------ Store ------
+
+/// Entities shared by all pages.
+///
+/// Pages keep only ids and read entities from the store, so a change made
+/// in one place (e.g. favorite or follow) is visible in all views.
+#[derive(Default)]
+pub struct Store {
+    articles: HashMap<Slug, Article>,
+    profiles: HashMap<Username<'static>, Author>,
+    comments: HashMap<CommentId, Comment>,
+}
+
+impl Store {
+    // ------ articles ------
+
+    pub fn article(&self, slug: &Slug) -> Option<&Article> {
+        self.articles.get(slug)
+    }
+
+    pub fn insert_article(&mut self, article: Article) -> Slug {
+        self.insert_author(article.author.clone());
+        let slug = article.slug.clone();
+        self.articles.insert(slug.clone(), article);
+        slug
+    }
+
+    pub fn insert_articles(
+        &mut self,
+        articles: PaginatedList<Article>,
+    ) -> PaginatedList<Slug> {
+        articles.map(|article| self.insert_article(article))
+    }
+
+    /// Change the article before the server confirms the change.
+    /// Returns the original article for a rollback.
+    pub fn change_article(
+        &mut self,
+        slug: &Slug,
+        change: impl FnOnce(&mut Article),
+    ) -> Option<Article> {
+        let original = self.articles.get(slug)?.clone();
+        let mut article = original.clone();
+        change(&mut article);
+        self.insert_article(article);
+        Some(original)
+    }
+
+    /// Roll back `favorited` changed by `change_article`.
+    /// Other fields keep the data received in the meantime.
+    pub fn restore_favorited(&mut self, original: &Article) {
+        self.change_article(&original.slug, |article| {
+            article.set_favorited(original.favorited)
+        });
+    }
+
+    pub fn remove_article(&mut self, slug: &Slug) {
+        self.articles.remove(slug);
+    }
+
+    // ------ profiles ------
+
+    pub fn author(&self, username: &Username) -> Option<&Author> {
+        self.profiles.get(username.as_str())
+    }
+
+    /// Articles and comments contain their authors - they are updated too.
+    pub fn insert_author(&mut self, author: Author) -> Username<'static> {
+        let username = author.username().to_static();
+        self.articles
+            .values_mut()
+            .filter(|article| article.author.username() == &username)
+            .for_each(|article| article.author = author.clone());
+        self.comments
+            .values_mut()
+            .filter(|comment| comment.author.username() == &username)
+            .for_each(|comment| comment.author = author.clone());
+        self.profiles.insert(username.clone(), author);
+        username
+    }
+
+    /// Change the author before the server confirms the change.
+    /// Returns the original author for a rollback.
+    pub fn change_author(
+        &mut self,
+        username: &Username,
+        change: impl FnOnce(&mut Author),
+    ) -> Option<Author> {
+        let original = self.author(username)?.clone();
+        let mut author = original.clone();
+        change(&mut author);
+        self.insert_author(author);
+        Some(original)
+    }
+
+    /// Roll back `following` changed by `change_author`.
+    pub fn restore_following(&mut self, original: &Author) {
+        let following = matches!(original, Author::Following(_));
+        self.change_author(original.username(), |author| {
+            author.set_following(following)
+        });
+    }
+
+    // ------ comments ------
+
+    pub fn comment(&self, comment_id: &CommentId) -> Option<&Comment> {
+        self.comments.get(comment_id)
+    }
+
+    pub fn insert_comment(&mut self, comment: Comment) -> CommentId {
+        self.insert_author(comment.author.clone());
+        let comment_id = comment.id.clone();
+        self.comments.insert(comment_id.clone(), comment);
+        comment_id
+    }
+
+    pub fn remove_comment(&mut self, comment_id: &CommentId) {
+        self.comments.remove(comment_id);
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::entity::{Avatar, Profile};
+    use chrono::Local;
+    use wasm_bindgen_test::*;
+
+    fn author(username: &'static str, following: bool) -> Author {
+        let profile = Profile {
+            bio: None,
+            avatar: Avatar::new(None::<&str>),
+            username: username.into(),
+        };
+        if following {
+            Author::Following(profile)
+        } else {
+            Author::NotFollowing(profile)
+        }
+    }
+
+    fn article(slug: &str, author: Author) -> Article {
+        Article {
+            title: slug.to_owned(),
+            slug: slug.to_owned().into(),
+            body: String::new().into(),
+            created_at: Local::now().into(),
+            updated_at: Local::now().into(),
+            tag_list: Vec::new(),
+            description: String::new(),
+            author,
+            favorited: false,
+            favorites_count: 0,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn insert_author_test() {
+        // ====== ARRANGE ======
+        let mut store = Store::default();
+        let first_slug =
+            store.insert_article(article("first", author("john", false)));
+        let second_slug =
+            store.insert_article(article("second", author("john", false)));
+
+        // ====== ACT ======
+        let username = store.insert_author(author("john", true));
+
+        // ====== ASSERT ======
+        let is_following =
+            |author: &Author| matches!(author, Author::Following(_));
+        assert!(is_following(store.author(&username).unwrap()));
+        assert!(is_following(&store.article(&first_slug).unwrap().author));
+        assert!(is_following(&store.article(&second_slug).unwrap().author));
+    }
+
+    #[wasm_bindgen_test]
+    fn restore_favorited_test() {
+        // ====== ARRANGE ======
+        let mut store = Store::default();
+        let slug =
+            store.insert_article(article("first", author("john", false)));
+        let original = store
+            .change_article(&slug, |article| article.set_favorited(true))
+            .unwrap();
+        store.change_article(&slug, |article| {
+            article.title = "updated".to_owned()
+        });
+
+        // ====== ACT ======
+        store.restore_favorited(&original);
+
+        // ====== ASSERT ======
+        let article = store.article(&slug).unwrap();
+        assert!(!article.favorited);
+        assert_eq!(article.favorites_count, 0);
+        assert_eq!(article.title, "updated");
+    }
+}
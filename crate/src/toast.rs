@@ -0,0 +1,61 @@
+use crate::entity::{ApiError, ErrorMessage};
+use gloo_timers::future::TimeoutFuture;
+use seed::prelude::*;
+use std::collections::VecDeque;
+
+const TOAST_DURATION_MS: u32 = 5000;
+
+// ------ Toast ------
+
+/// Short-lived notification displayed above all pages.
+pub struct Toast {
+    title: String,
+    messages: Vec<ErrorMessage>,
+}
+
+impl Toast {
+    pub fn new_error(title: impl Into<String>, error: ApiError) -> Self {
+        Self {
+            title: title.into(),
+            messages: error.into_error_messages(),
+        }
+    }
+}
+
+pub async fn notify_on_expiration<Ms>(msg: Ms) -> Result<Ms, Ms> {
+    TimeoutFuture::new(TOAST_DURATION_MS).await;
+    Ok(msg)
+}
+
+// ------ view functions ------
+
+pub fn view<Ms>(toasts: &VecDeque<Toast>) -> Node<Ms> {
+    if toasts.is_empty() {
+        return empty![];
+    }
+    div![
+        style! {
+            "position" => "fixed",
+            "right" => "20px",
+            "bottom" => "20px",
+            "z-index" => 9999,
+        },
+        toasts.iter().map(view_toast)
+    ]
+}
+
+// ====== PRIVATE ======
+
+fn view_toast<Ms>(toast: &Toast) -> Node<Ms> {
+    div![
+        class!["error-messages"],
+        style! {
+            "background" => "rgb(250, 250, 250)",
+            "padding" => "10px 20px",
+            "margin-top" => "10px",
+            "border" => "1px solid",
+        },
+        strong![toast.title],
+        toast.messages.iter().map(|message| p![message])
+    ]
+}
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub type UserId = usize;
+pub type CommentId = usize;
+
+// ------ User ------
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct User {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    // It's a development server - passwords are stored as plain text.
+    pub password: String,
+    pub bio: Option<String>,
+    pub image: Option<String>,
+}
+
+// ------ Article ------
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Article {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub body: String,
+    pub tag_list: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub author_id: UserId,
+    pub favorited_by: BTreeSet<UserId>,
+}
+
+// ------ Comment ------
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: CommentId,
+    pub article_slug: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub author_id: UserId,
+}
+
+// ------ Db ------
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Db {
+    #[serde(skip)]
+    file: Option<PathBuf>,
+    next_id: usize,
+    pub users: Vec<User>,
+    /// Auth token -> user.
+    pub tokens: BTreeMap<String, UserId>,
+    /// (follower, followed)
+    pub follows: BTreeSet<(UserId, UserId)>,
+    /// Newest articles are at the end.
+    pub articles: Vec<Article>,
+    pub comments: Vec<Comment>,
+}
+
+impl Db {
+    /// Load the database from `file` (if it exists) and keep the path
+    /// for `save`.
+    pub fn open(file: impl Into<PathBuf>) -> io::Result<Self> {
+        let file = file.into();
+        let mut db = if file.exists() {
+            serde_json::from_str(&fs::read_to_string(&file)?)?
+        } else {
+            Self::default()
+        };
+        db.file = Some(file);
+        Ok(db)
+    }
+
+    /// Persist the database if it has been opened from a file.
+    pub fn save(&self) -> io::Result<()> {
+        match &self.file {
+            Some(file) => write_json(file, self),
+            None => Ok(()),
+        }
+    }
+
+    pub fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    // ------ users ------
+
+    pub fn user(&self, id: UserId) -> Option<&User> {
+        self.users.iter().find(|user| user.id == id)
+    }
+
+    pub fn user_mut(&mut self, id: UserId) -> Option<&mut User> {
+        self.users.iter_mut().find(|user| user.id == id)
+    }
+
+    pub fn user_by_username(&self, username: &str) -> Option<&User> {
+        self.users.iter().find(|user| user.username == username)
+    }
+
+    pub fn user_by_email(&self, email: &str) -> Option<&User> {
+        self.users.iter().find(|user| user.email == email)
+    }
+
+    pub fn user_by_token(&self, token: &str) -> Option<&User> {
+        self.tokens.get(token).and_then(|id| self.user(*id))
+    }
+
+    pub fn issue_token(&mut self, user_id: UserId) -> String {
+        let token = format!("local-token-{}-{}", user_id, self.next_id());
+        self.tokens.insert(token.clone(), user_id);
+        token
+    }
+
+    pub fn is_following(
+        &self,
+        follower: Option<UserId>,
+        followed: UserId,
+    ) -> bool {
+        follower.is_some_and(|follower| {
+            self.follows.contains(&(follower, followed))
+        })
+    }
+
+    // ------ articles ------
+
+    pub fn article(&self, slug: &str) -> Option<&Article> {
+        self.articles.iter().find(|article| article.slug == slug)
+    }
+
+    pub fn article_mut(&mut self, slug: &str) -> Option<&mut Article> {
+        self.articles.iter_mut().find(|article| article.slug == slug)
+    }
+
+    /// Create a slug from the `title` that isn't used by any other article.
+    pub fn unique_slug(&self, title: &str) -> String {
+        let base = slugify(title);
+        let mut slug = base.clone();
+        let mut suffix = 1;
+        while self.article(&slug).is_some() {
+            suffix += 1;
+            slug = format!("{}-{}", base, suffix);
+        }
+        slug
+    }
+}
+
+// ====== PRIVATE ======
+
+fn write_json(file: &Path, db: &Db) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(db)?;
+    fs::write(file, json)
+}
+
+fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "article".into()
+    } else {
+        slug
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_test() {
+        // ====== ACT ======
+        let slug = slugify("  Hello, Rust & Seed! ");
+
+        // ====== ASSERT ======
+        assert_eq!(slug, "hello-rust-seed");
+    }
+
+    #[test]
+    fn unique_slug_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+        db.articles.push(Article {
+            slug: "title".into(),
+            title: "Title".into(),
+            description: String::new(),
+            body: String::new(),
+            tag_list: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            author_id: 1,
+            favorited_by: BTreeSet::new(),
+        });
+
+        // ====== ACT ======
+        let slug = db.unique_slug("Title");
+
+        // ====== ASSERT ======
+        assert_eq!(slug, "title-2");
+    }
+}
@@ -0,0 +1,61 @@
+//! JSON shapes mirror the frontend's `coder::decoder` structs.
+
+use crate::db::{Article, Comment, Db, User, UserId};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+pub fn user(user: &User, token: &str) -> Value {
+    json!({
+        "email": user.email,
+        "token": token,
+        "username": user.username,
+        "bio": user.bio,
+        "image": user.image,
+    })
+}
+
+pub fn profile(db: &Db, user: &User, viewer: Option<UserId>) -> Value {
+    json!({
+        "username": user.username,
+        "bio": user.bio,
+        // the frontend's `Author` decoder requires a string
+        "image": user.image.clone().unwrap_or_default(),
+        "following": db.is_following(viewer, user.id),
+    })
+}
+
+pub fn article(db: &Db, article: &Article, viewer: Option<UserId>) -> Value {
+    json!({
+        "slug": article.slug,
+        "title": article.title,
+        "description": article.description,
+        "body": article.body,
+        "tagList": article.tag_list,
+        "createdAt": timestamp(article.created_at),
+        "updatedAt": timestamp(article.updated_at),
+        "favorited": viewer.is_some_and(|viewer| article.favorited_by.contains(&viewer)),
+        "favoritesCount": article.favorited_by.len(),
+        "author": author(db, article.author_id, viewer),
+    })
+}
+
+pub fn comment(db: &Db, comment: &Comment, viewer: Option<UserId>) -> Value {
+    json!({
+        "id": comment.id,
+        "createdAt": timestamp(comment.created_at),
+        "updatedAt": timestamp(comment.updated_at),
+        "body": comment.body,
+        "author": author(db, comment.author_id, viewer),
+    })
+}
+
+// ====== PRIVATE ======
+
+fn author(db: &Db, author_id: UserId, viewer: Option<UserId>) -> Value {
+    db.user(author_id).map_or(Value::Null, |author| profile(db, author, viewer))
+}
+
+// "2019-07-24T08:21:36.453Z"
+fn timestamp(date_time: DateTime<Utc>) -> String {
+    date_time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
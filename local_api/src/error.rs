@@ -0,0 +1,84 @@
+use serde_json::{json, Value};
+
+// ------ ApiError ------
+
+/// Errors in the shape expected by RealWorld clients:
+/// `{"errors": {"<field>": ["<message>", ..]}}`.
+#[derive(Debug, PartialEq)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized,
+    Forbidden,
+    NotFound(&'static str),
+    Validation(Vec<(&'static str, String)>),
+}
+
+impl ApiError {
+    pub fn invalid(field: &'static str, message: impl Into<String>) -> Self {
+        Self::Validation(vec![(field, message.into())])
+    }
+
+    pub const fn status_code(&self) -> u16 {
+        match self {
+            Self::BadRequest(_) => 400,
+            Self::Unauthorized => 401,
+            Self::Forbidden => 403,
+            Self::NotFound(_) => 404,
+            Self::Validation(_) => 422,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let errors = match self {
+            Self::BadRequest(message) => vec![("body", message.clone())],
+            Self::Unauthorized => {
+                vec![("token", "is missing or invalid".to_owned())]
+            },
+            Self::Forbidden => vec![("user", "is not the owner".to_owned())],
+            Self::NotFound(subject) => vec![(*subject, "not found".to_owned())],
+            Self::Validation(errors) => errors.clone(),
+        };
+
+        let mut fields = serde_json::Map::new();
+        for (field, message) in errors {
+            fields
+                .entry(field)
+                .or_insert_with(|| json!([]))
+                .as_array_mut()
+                .expect("error messages have to be an array")
+                .push(message.into());
+        }
+        json!({ "errors": fields })
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_to_json_test() {
+        // ====== ARRANGE ======
+        let error = ApiError::Validation(vec![
+            ("email", "can't be blank".into()),
+            ("email", "is invalid".into()),
+            ("username", "has already been taken".into()),
+        ]);
+
+        // ====== ACT ======
+        let json = error.to_json();
+
+        // ====== ASSERT ======
+        assert_eq!(
+            json,
+            json!({
+                "errors": {
+                    "email": ["can't be blank", "is invalid"],
+                    "username": ["has already been taken"],
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    db::{Article, Db, UserId},
+    encoder,
+    error::ApiError,
+    router::Context,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Deserialize)]
+struct RootDecoder<T> {
+    article: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewArticle {
+    title: String,
+    description: String,
+    body: String,
+    #[serde(default)]
+    tag_list: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArticleUpdate {
+    title: Option<String>,
+    description: Option<String>,
+    body: Option<String>,
+    tag_list: Option<Vec<String>>,
+}
+
+pub fn load_list(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let author = context
+        .query("author")
+        .map(|username| db.user_by_username(username).map(|user| user.id));
+    let favorited_by = context
+        .query("favorited")
+        .map(|username| db.user_by_username(username).map(|user| user.id));
+    let tag = context.query("tag");
+
+    let articles = db
+        .articles
+        .iter()
+        .rev()
+        .filter(|article| {
+            author.is_none_or(|author| Some(article.author_id) == author)
+        })
+        .filter(|article| {
+            favorited_by.is_none_or(|user| {
+                user.is_some_and(|user| article.favorited_by.contains(&user))
+            })
+        })
+        .filter(|article| {
+            tag.is_none_or(|tag| article.tag_list.iter().any(|t| t == tag))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(paginated_articles(db, context, &articles))
+}
+
+pub fn load_feed(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let articles = db
+        .articles
+        .iter()
+        .rev()
+        .filter(|article| db.is_following(Some(viewer), article.author_id))
+        .collect::<Vec<_>>();
+
+    Ok(paginated_articles(db, context, &articles))
+}
+
+pub fn load(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let article = db.article(slug).ok_or(ApiError::NotFound("article"))?;
+    Ok(json!({ "article": encoder::article(db, article, context.viewer()) }))
+}
+
+pub fn create(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let new_article = context.body::<RootDecoder<NewArticle>>()?.article;
+
+    let mut errors = Vec::new();
+    if new_article.title.trim().is_empty() {
+        errors.push(("title", "can't be blank".to_owned()));
+    }
+    if new_article.body.trim().is_empty() {
+        errors.push(("body", "can't be blank".to_owned()));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let slug = db.unique_slug(&new_article.title);
+    let now = Utc::now();
+    db.articles.push(Article {
+        slug: slug.clone(),
+        title: new_article.title,
+        description: new_article.description,
+        body: new_article.body,
+        tag_list: clean_tags(new_article.tag_list),
+        created_at: now,
+        updated_at: now,
+        author_id: viewer,
+        favorited_by: BTreeSet::new(),
+    });
+    load(db, context, &slug)
+}
+
+/// The slug stays the same even if the title is changed.
+pub fn update(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let update = context.body::<RootDecoder<ArticleUpdate>>()?.article;
+    let article = owned_article(db, viewer, slug)?;
+
+    if let Some(title) = update.title {
+        if title.trim().is_empty() {
+            return Err(ApiError::invalid("title", "can't be blank"));
+        }
+        article.title = title;
+    }
+    if let Some(body) = update.body {
+        if body.trim().is_empty() {
+            return Err(ApiError::invalid("body", "can't be blank"));
+        }
+        article.body = body;
+    }
+    if let Some(description) = update.description {
+        article.description = description;
+    }
+    if let Some(tag_list) = update.tag_list {
+        article.tag_list = clean_tags(tag_list);
+    }
+    article.updated_at = Utc::now();
+    load(db, context, slug)
+}
+
+pub fn delete(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    owned_article(db, viewer, slug)?;
+
+    db.articles.retain(|article| article.slug != slug);
+    db.comments.retain(|comment| comment.article_slug != slug);
+    Ok(json!({}))
+}
+
+pub fn favorite(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    db.article_mut(slug)
+        .ok_or(ApiError::NotFound("article"))?
+        .favorited_by
+        .insert(viewer);
+    load(db, context, slug)
+}
+
+pub fn unfavorite(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    db.article_mut(slug)
+        .ok_or(ApiError::NotFound("article"))?
+        .favorited_by
+        .remove(&viewer);
+    load(db, context, slug)
+}
+
+// ====== PRIVATE ======
+
+fn owned_article<'a>(
+    db: &'a mut Db,
+    viewer: UserId,
+    slug: &str,
+) -> Result<&'a mut Article, ApiError> {
+    let article = db.article_mut(slug).ok_or(ApiError::NotFound("article"))?;
+    if article.author_id == viewer {
+        Ok(article)
+    } else {
+        Err(ApiError::Forbidden)
+    }
+}
+
+fn paginated_articles(
+    db: &Db,
+    context: &Context,
+    articles: &[&Article],
+) -> Value {
+    let limit = context
+        .query("limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT);
+    let offset = context
+        .query("offset")
+        .and_then(|offset| offset.parse().ok())
+        .unwrap_or_default();
+
+    json!({
+        "articles": articles
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|article| encoder::article(db, article, context.viewer()))
+            .collect::<Vec<_>>(),
+        "articlesCount": articles.len(),
+    })
+}
+
+fn clean_tags(tags: Vec<String>) -> Vec<String> {
+    let mut unique_tags = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !unique_tags.iter().any(|t| t == tag) {
+            unique_tags.push(tag.to_owned());
+        }
+    }
+    unique_tags
+}
@@ -0,0 +1,85 @@
+use crate::{
+    db::{Comment, CommentId, Db},
+    encoder,
+    error::ApiError,
+    router::Context,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct RootDecoder {
+    comment: NewComment,
+}
+
+#[derive(Deserialize)]
+struct NewComment {
+    body: String,
+}
+
+/// Newest comments first.
+pub fn load_list(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    db.article(slug).ok_or(ApiError::NotFound("article"))?;
+    let comments = db
+        .comments
+        .iter()
+        .rev()
+        .filter(|comment| comment.article_slug == slug)
+        .map(|comment| encoder::comment(db, comment, context.viewer()))
+        .collect::<Vec<_>>();
+    Ok(json!({ "comments": comments }))
+}
+
+pub fn create(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let new_comment = context.body::<RootDecoder>()?.comment;
+    db.article(slug).ok_or(ApiError::NotFound("article"))?;
+    if new_comment.body.trim().is_empty() {
+        return Err(ApiError::invalid("body", "can't be blank"));
+    }
+
+    let now = Utc::now();
+    let comment = Comment {
+        id: db.next_id(),
+        article_slug: slug.to_owned(),
+        body: new_comment.body,
+        created_at: now,
+        updated_at: now,
+        author_id: viewer,
+    };
+    let json =
+        json!({ "comment": encoder::comment(db, &comment, Some(viewer)) });
+    db.comments.push(comment);
+    Ok(json)
+}
+
+pub fn delete(
+    db: &mut Db,
+    context: &Context,
+    slug: &str,
+    id: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let id =
+        id.parse::<CommentId>().map_err(|_| ApiError::NotFound("comment"))?;
+    let comment = db
+        .comments
+        .iter()
+        .find(|comment| comment.id == id && comment.article_slug == slug)
+        .ok_or(ApiError::NotFound("comment"))?;
+    if comment.author_id != viewer {
+        return Err(ApiError::Forbidden);
+    }
+
+    db.comments.retain(|comment| comment.id != id);
+    Ok(json!({}))
+}
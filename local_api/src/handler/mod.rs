@@ -0,0 +1,5 @@
+pub mod article;
+pub mod comment;
+pub mod profile;
+pub mod tag;
+pub mod user;
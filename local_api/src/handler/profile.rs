@@ -0,0 +1,36 @@
+use crate::{db::Db, encoder, error::ApiError, router::Context};
+use serde_json::{json, Value};
+
+pub fn load(
+    db: &mut Db,
+    context: &Context,
+    username: &str,
+) -> Result<Value, ApiError> {
+    let user =
+        db.user_by_username(username).ok_or(ApiError::NotFound("profile"))?;
+    Ok(json!({ "profile": encoder::profile(db, user, context.viewer()) }))
+}
+
+pub fn follow(
+    db: &mut Db,
+    context: &Context,
+    username: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let user_id =
+        db.user_by_username(username).ok_or(ApiError::NotFound("profile"))?.id;
+    db.follows.insert((viewer, user_id));
+    load(db, context, username)
+}
+
+pub fn unfollow(
+    db: &mut Db,
+    context: &Context,
+    username: &str,
+) -> Result<Value, ApiError> {
+    let (viewer, _) = context.authenticated()?;
+    let user_id =
+        db.user_by_username(username).ok_or(ApiError::NotFound("profile"))?.id;
+    db.follows.remove(&(viewer, user_id));
+    load(db, context, username)
+}
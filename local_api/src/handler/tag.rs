@@ -0,0 +1,17 @@
+use crate::{db::Db, error::ApiError};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Tags ordered by the number of articles using them.
+pub fn load_list(db: &mut Db) -> Result<Value, ApiError> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for tag in db.articles.iter().flat_map(|article| &article.tag_list) {
+        *counts.entry(tag).or_default() += 1;
+    }
+    let mut tags = counts.into_iter().collect::<Vec<_>>();
+    tags.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    Ok(json!({
+        "tags": tags.into_iter().map(|(tag, _)| tag).collect::<Vec<_>>()
+    }))
+}
@@ -0,0 +1,139 @@
+use crate::{
+    db::{Db, User},
+    encoder,
+    error::ApiError,
+    router::Context,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct RootDecoder<T> {
+    user: T,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    email: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct Registration {
+    username: String,
+    email: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    username: Option<String>,
+    email: Option<String>,
+    password: Option<String>,
+    bio: Option<String>,
+    image: Option<String>,
+}
+
+pub fn login(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let login = context.body::<RootDecoder<Login>>()?.user;
+    let user_id = db
+        .user_by_email(&login.email)
+        .filter(|user| user.password == login.password)
+        .map(|user| user.id)
+        .ok_or_else(|| ApiError::invalid("email or password", "is invalid"))?;
+
+    let token = db.issue_token(user_id);
+    Ok(user_response(db, user_id, &token))
+}
+
+pub fn register(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let registration = context.body::<RootDecoder<Registration>>()?.user;
+
+    let mut errors = Vec::new();
+    if registration.username.trim().is_empty() {
+        errors.push(("username", "can't be blank".to_owned()));
+    } else if db.user_by_username(&registration.username).is_some() {
+        errors.push(("username", "has already been taken".to_owned()));
+    }
+    if registration.email.trim().is_empty() {
+        errors.push(("email", "can't be blank".to_owned()));
+    } else if db.user_by_email(&registration.email).is_some() {
+        errors.push(("email", "has already been taken".to_owned()));
+    }
+    if registration.password.is_empty() {
+        errors.push(("password", "can't be blank".to_owned()));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let user_id = db.next_id();
+    db.users.push(User {
+        id: user_id,
+        username: registration.username,
+        email: registration.email,
+        password: registration.password,
+        bio: None,
+        image: None,
+    });
+    let token = db.issue_token(user_id);
+    Ok(user_response(db, user_id, &token))
+}
+
+pub fn current(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let (user_id, token) = context.authenticated()?;
+    Ok(user_response(db, user_id, token))
+}
+
+pub fn update(db: &mut Db, context: &Context) -> Result<Value, ApiError> {
+    let (user_id, token) = context.authenticated()?;
+    let update = context.body::<RootDecoder<Update>>()?.user;
+
+    if let Some(username) = &update.username {
+        if username.trim().is_empty() {
+            return Err(ApiError::invalid("username", "can't be blank"));
+        }
+        if db.user_by_username(username).is_some_and(|user| user.id != user_id)
+        {
+            return Err(ApiError::invalid(
+                "username",
+                "has already been taken",
+            ));
+        }
+    }
+    if let Some(email) = &update.email {
+        if email.trim().is_empty() {
+            return Err(ApiError::invalid("email", "can't be blank"));
+        }
+        if db.user_by_email(email).is_some_and(|user| user.id != user_id) {
+            return Err(ApiError::invalid("email", "has already been taken"));
+        }
+    }
+
+    let user = db.user_mut(user_id).ok_or(ApiError::Unauthorized)?;
+    if let Some(username) = update.username {
+        user.username = username;
+    }
+    if let Some(email) = update.email {
+        user.email = email;
+    }
+    if let Some(password) =
+        update.password.filter(|password| !password.is_empty())
+    {
+        user.password = password;
+    }
+    if let Some(bio) = update.bio {
+        user.bio = Some(bio).filter(|bio| !bio.is_empty());
+    }
+    if let Some(image) = update.image {
+        user.image = Some(image).filter(|image| !image.is_empty());
+    }
+    Ok(user_response(db, user_id, token))
+}
+
+// ====== PRIVATE ======
+
+fn user_response(db: &Db, user_id: usize, token: &str) -> Value {
+    let user = db.user(user_id).expect("user has to exist");
+    json!({ "user": encoder::user(user, token) })
+}
@@ -0,0 +1,145 @@
+//! RealWorld API stand-in server for offline development.
+//!
+//! It implements the endpoints used by the Seed frontend with the same JSON
+//! shapes as the Dark backend.
+//!
+//! ```text
+//! cargo run -- [--port 3000] [--data db.json]
+//! ```
+//!
+//! Data are kept in memory, or in the given JSON file when `--data` is set.
+//! Start the frontend with the `local` config profile to use this server.
+
+use std::{env, process};
+use tiny_http::{Header, Server};
+
+mod db;
+mod encoder;
+mod error;
+mod handler;
+mod router;
+
+const DEFAULT_PORT: u16 = 3000;
+
+// ------ Args ------
+
+struct Args {
+    port: u16,
+    data: Option<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self {
+            port: DEFAULT_PORT,
+            data: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value =
+                || args.next().ok_or(format!("missing value for {}", arg));
+            match arg.as_str() {
+                "--port" => {
+                    parsed.port = value()?
+                        .parse()
+                        .map_err(|_| "--port has to be a number".to_owned())?
+                },
+                "--data" => parsed.data = Some(value()?),
+                _ => return Err(format!("unknown argument {}", arg)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+// ------ main ------
+
+fn main() {
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{}\nusage: local_api [--port 3000] [--data db.json]", error);
+        process::exit(2);
+    });
+
+    let mut db = match &args.data {
+        Some(file) => db::Db::open(file).unwrap_or_else(|error| {
+            eprintln!("cannot open {}: {}", file, error);
+            process::exit(1);
+        }),
+        None => db::Db::default(),
+    };
+
+    let server =
+        Server::http(("127.0.0.1", args.port)).unwrap_or_else(|error| {
+            eprintln!("cannot start server: {}", error);
+            process::exit(1);
+        });
+    println!("RealWorld API listening on http://127.0.0.1:{}/api", args.port);
+
+    for mut request in server.incoming_requests() {
+        let method = match request.method().as_str().parse::<router::Method>() {
+            Ok(method) => method,
+            Err(()) => {
+                let _ = request.respond(tiny_http::Response::empty(405));
+                continue;
+            },
+        };
+
+        let mut body = String::new();
+        if let Err(error) = request.as_reader().read_to_string(&mut body) {
+            eprintln!("cannot read request body: {}", error);
+        }
+        let token = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("authorization"))
+            .and_then(|header| {
+                header
+                    .value
+                    .as_str()
+                    .strip_prefix("Token ")
+                    .map(ToOwned::to_owned)
+            });
+
+        let response = router::handle(
+            &mut db,
+            router::Request {
+                method,
+                url: request.url().to_owned(),
+                token,
+                body,
+            },
+        );
+        println!(
+            "{} {} -> {}",
+            request.method(),
+            request.url(),
+            response.status
+        );
+
+        if !method.is_safe() && response.status < 400 {
+            if let Err(error) = db.save() {
+                eprintln!("cannot save data: {}", error);
+            }
+        }
+
+        let mut http_response =
+            tiny_http::Response::from_string(if response.body.is_null() {
+                String::new()
+            } else {
+                response.body.to_string()
+            })
+            .with_status_code(response.status);
+        for header in &[
+            "Content-Type: application/json; charset=utf-8",
+            "Access-Control-Allow-Origin: *",
+            "Access-Control-Allow-Headers: authorization, content-type",
+            "Access-Control-Allow-Methods: GET, POST, PUT, DELETE, OPTIONS",
+        ] {
+            http_response.add_header(
+                header.parse::<Header>().expect("invalid static header"),
+            );
+        }
+        if let Err(error) = request.respond(http_response) {
+            eprintln!("cannot send response: {}", error);
+        }
+    }
+}
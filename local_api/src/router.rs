@@ -0,0 +1,409 @@
+use crate::{
+    db::{Db, UserId},
+    error::ApiError,
+    handler,
+};
+use percent_encoding::percent_decode_str;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::str::FromStr;
+
+const API_PREFIX: &str = "/api/";
+
+// ------ Method ------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Options,
+}
+
+impl Method {
+    /// Requests with other methods may change the database.
+    pub const fn is_safe(self) -> bool {
+        match self {
+            Self::Get | Self::Options => true,
+            Self::Post | Self::Put | Self::Delete => false,
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = ();
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        match method.to_ascii_uppercase().as_str() {
+            "GET" => Ok(Self::Get),
+            "POST" => Ok(Self::Post),
+            "PUT" => Ok(Self::Put),
+            "DELETE" => Ok(Self::Delete),
+            "OPTIONS" => Ok(Self::Options),
+            _ => Err(()),
+        }
+    }
+}
+
+// ------ Request ------
+
+pub struct Request {
+    pub method: Method,
+    /// Path and query, e.g. `/api/articles?limit=10`.
+    pub url: String,
+    /// Value of the `Authorization: Token <token>` header.
+    pub token: Option<String>,
+    pub body: String,
+}
+
+// ------ Response ------
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+// ------ Context ------
+
+/// Request data passed to handlers.
+pub struct Context {
+    viewer: Option<UserId>,
+    token: Option<String>,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+impl Context {
+    pub const fn viewer(&self) -> Option<UserId> {
+        self.viewer
+    }
+
+    pub fn authenticated(&self) -> Result<(UserId, &str), ApiError> {
+        match (self.viewer, &self.token) {
+            (Some(viewer), Some(token)) => Ok((viewer, token)),
+            _ => Err(ApiError::Unauthorized),
+        }
+    }
+
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn body<T: DeserializeOwned>(&self) -> Result<T, ApiError> {
+        serde_json::from_str(&self.body)
+            .map_err(|error| ApiError::BadRequest(error.to_string()))
+    }
+}
+
+// ------ handle ------
+
+pub fn handle(db: &mut Db, request: Request) -> Response {
+    if request.method == Method::Options {
+        return Response {
+            status: 204,
+            body: Value::Null,
+        };
+    }
+
+    let (path, query) = match request.url.find('?') {
+        Some(index) => request.url.split_at(index),
+        None => (request.url.as_str(), ""),
+    };
+    let segments = path
+        .trim_start_matches(API_PREFIX)
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            percent_decode_str(segment).decode_utf8_lossy().into_owned()
+        })
+        .collect::<Vec<_>>();
+
+    let context = Context {
+        viewer: request
+            .token
+            .as_ref()
+            .and_then(|token| db.user_by_token(token))
+            .map(|user| user.id),
+        token: request.token,
+        query: form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+            .into_owned()
+            .collect(),
+        body: request.body,
+    };
+
+    let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+    match route(db, request.method, &segments, &context) {
+        Ok(body) => Response {
+            status: 200,
+            body,
+        },
+        Err(error) => Response {
+            status: error.status_code(),
+            body: error.to_json(),
+        },
+    }
+}
+
+// ====== PRIVATE ======
+
+fn route(
+    db: &mut Db,
+    method: Method,
+    segments: &[&str],
+    context: &Context,
+) -> Result<Value, ApiError> {
+    use handler::{article, comment, profile, tag, user};
+    use Method::*;
+
+    match (method, segments) {
+        (Post, ["users", "login"]) => user::login(db, context),
+        (Post, ["users"]) => user::register(db, context),
+        (Get, ["user"]) => user::current(db, context),
+        (Put, ["user"]) => user::update(db, context),
+
+        (Get, ["profiles", username]) => profile::load(db, context, username),
+        (Post, ["profiles", username, "follow"]) => {
+            profile::follow(db, context, username)
+        },
+        (Delete, ["profiles", username, "follow"]) => {
+            profile::unfollow(db, context, username)
+        },
+
+        (Get, ["articles"]) => article::load_list(db, context),
+        (Get, ["articles", "feed"]) => article::load_feed(db, context),
+        (Post, ["articles"]) => article::create(db, context),
+        (Get, ["articles", slug]) => article::load(db, context, slug),
+        (Put, ["articles", slug]) => article::update(db, context, slug),
+        (Delete, ["articles", slug]) => article::delete(db, context, slug),
+        (Post, ["articles", slug, "favorite"]) => {
+            article::favorite(db, context, slug)
+        },
+        (Delete, ["articles", slug, "favorite"]) => {
+            article::unfavorite(db, context, slug)
+        },
+
+        (Get, ["articles", slug, "comments"]) => {
+            comment::load_list(db, context, slug)
+        },
+        (Post, ["articles", slug, "comments"]) => {
+            comment::create(db, context, slug)
+        },
+        (Delete, ["articles", slug, "comments", id]) => {
+            comment::delete(db, context, slug, id)
+        },
+
+        (Get, ["tags"]) => tag::load_list(db),
+
+        _ => Err(ApiError::NotFound("route")),
+    }
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub fn request(
+        db: &mut Db,
+        method: Method,
+        url: &str,
+        token: Option<&str>,
+        body: Value,
+    ) -> Response {
+        handle(
+            db,
+            Request {
+                method,
+                url: url.into(),
+                token: token.map(ToOwned::to_owned),
+                body: body.to_string(),
+            },
+        )
+    }
+
+    /// Register a user and return their auth token.
+    pub fn register(db: &mut Db, username: &str) -> String {
+        let response = request(
+            db,
+            Method::Post,
+            "/api/users",
+            None,
+            json!({ "user": {
+                "username": username,
+                "email": format!("{}@example.com", username),
+                "password": "12345678",
+            }}),
+        );
+        response.body["user"]["token"]
+            .as_str()
+            .expect("registration failed")
+            .to_owned()
+    }
+
+    #[test]
+    fn unknown_route_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+
+        // ====== ACT ======
+        let response =
+            request(&mut db, Method::Get, "/api/unknown", None, Value::Null);
+
+        // ====== ASSERT ======
+        assert_eq!(response.status, 404);
+    }
+
+    #[test]
+    fn register_login_and_load_user_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+        register(&mut db, "john");
+
+        // ====== ACT ======
+        let login = request(
+            &mut db,
+            Method::Post,
+            "/api/users/login",
+            None,
+            json!({ "user": {
+                "email": "john@example.com",
+                "password": "12345678",
+            }}),
+        );
+        let token = login.body["user"]["token"].as_str().unwrap();
+        let user = request(
+            &mut db,
+            Method::Get,
+            "/api/user",
+            Some(token),
+            Value::Null,
+        );
+
+        // ====== ASSERT ======
+        assert_eq!(user.status, 200);
+        assert_eq!(user.body["user"]["username"], "john");
+        assert_eq!(user.body["user"]["email"], "john@example.com");
+    }
+
+    #[test]
+    fn missing_token_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+
+        // ====== ACT ======
+        let response =
+            request(&mut db, Method::Get, "/api/user", None, Value::Null);
+
+        // ====== ASSERT ======
+        assert_eq!(response.status, 401);
+    }
+
+    #[test]
+    fn article_flow_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+        let author = register(&mut db, "john");
+        let reader = register(&mut db, "jane");
+
+        // ====== ACT ======
+        let created = request(
+            &mut db,
+            Method::Post,
+            "/api/articles",
+            Some(&author),
+            json!({ "article": {
+                "title": "Hello World",
+                "description": "first",
+                "body": "# Hi",
+                "tagList": ["rust", "seed", ""],
+            }}),
+        );
+        request(
+            &mut db,
+            Method::Post,
+            "/api/profiles/john/follow",
+            Some(&reader),
+            Value::Null,
+        );
+        request(
+            &mut db,
+            Method::Post,
+            "/api/articles/hello-world/favorite",
+            Some(&reader),
+            Value::Null,
+        );
+        request(
+            &mut db,
+            Method::Post,
+            "/api/articles/hello-world/comments",
+            Some(&reader),
+            json!({ "comment": { "body": "Nice!" } }),
+        );
+        let feed = request(
+            &mut db,
+            Method::Get,
+            "/api/articles/feed?limit=10&offset=0",
+            Some(&reader),
+            Value::Null,
+        );
+        let by_tag = request(
+            &mut db,
+            Method::Get,
+            "/api/articles?tag=seed&limit=10&offset=0",
+            None,
+            Value::Null,
+        );
+        let comments = request(
+            &mut db,
+            Method::Get,
+            "/api/articles/hello-world/comments",
+            None,
+            Value::Null,
+        );
+        let tags =
+            request(&mut db, Method::Get, "/api/tags", None, Value::Null);
+
+        // ====== ASSERT ======
+        assert_eq!(created.body["article"]["slug"], "hello-world");
+        assert_eq!(feed.body["articlesCount"], 1);
+        assert_eq!(feed.body["articles"][0]["favorited"], true);
+        assert_eq!(feed.body["articles"][0]["author"]["following"], true);
+        assert_eq!(by_tag.body["articles"][0]["favoritesCount"], 1);
+        assert_eq!(comments.body["comments"][0]["body"], "Nice!");
+        assert_eq!(tags.body, json!({ "tags": ["rust", "seed"] }));
+    }
+
+    #[test]
+    fn delete_foreign_article_test() {
+        // ====== ARRANGE ======
+        let mut db = Db::default();
+        let author = register(&mut db, "john");
+        let other = register(&mut db, "jane");
+        request(
+            &mut db,
+            Method::Post,
+            "/api/articles",
+            Some(&author),
+            json!({ "article": { "title": "Mine", "description": "", "body": "b" }}),
+        );
+
+        // ====== ACT ======
+        let response = request(
+            &mut db,
+            Method::Delete,
+            "/api/articles/mine",
+            Some(&other),
+            Value::Null,
+        );
+
+        // ====== ASSERT ======
+        assert_eq!(response.status, 403);
+    }
+}